@@ -346,8 +346,9 @@ pub async fn track_authenticated_proof_analytics(
     task: Task,
     environment: Environment,
     client_id: String,
+    verified_locally: bool,
 ) {
-    let analytics_data = match task.program_id.as_str() {
+    let mut analytics_data = match task.program_id.as_str() {
         "fib_input_initial" => {
             // For fib_input_initial, extract the triple inputs from the first input
             let all_inputs = task.all_inputs();
@@ -397,6 +398,7 @@ pub async fn track_authenticated_proof_analytics(
             })
         }
     };
+    analytics_data["verified_locally"] = json!(verified_locally);
 
     let _ = track(
         vec!["cli_proof_node_v4".to_string(), "proof_node".to_string()],
@@ -0,0 +1,132 @@
+//! Crash-safe file writes
+//!
+//! [`write_atomic`] writes a file under a temporary name, syncs it and renames it over the
+//! target, so readers see either the old contents or the new ones and never a torn file.
+//! [`FileLock`] serializes writers across processes with an OS advisory lock (`flock` or
+//! `LockFileEx`) on a `<file>.lock` next to the target. The lock file is never removed, so
+//! every process always locks the same file, and the OS releases the lock when its holder
+//! exits, however it exits.
+
+use crate::consts::cli_consts::{FILE_LOCK_POLL_MS, FILE_LOCK_TIMEOUT_SECS};
+use fs4::fs_std::FileExt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Exclusive advisory lock on a file, released when dropped
+#[derive(Debug)]
+pub struct FileLock {
+    file: File,
+}
+
+impl FileLock {
+    /// Lock `target`, waiting for another holder to finish without blocking the runtime
+    pub async fn acquire(target: &Path) -> io::Result<Self> {
+        Self::acquire_within(target, Duration::from_secs(FILE_LOCK_TIMEOUT_SECS)).await
+    }
+
+    async fn acquire_within(target: &Path, timeout: Duration) -> io::Result<Self> {
+        let path = lock_path(target);
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+        let started = Instant::now();
+        while !FileExt::try_lock_exclusive(&file)? {
+            if started.elapsed() >= timeout {
+                return Err(io::Error::new(
+                    io::ErrorKind::WouldBlock,
+                    format!("{} is locked by another process", target.display()),
+                ));
+            }
+            tokio::time::sleep(Duration::from_millis(FILE_LOCK_POLL_MS)).await;
+        }
+        Ok(Self { file })
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = FileExt::unlock(&self.file);
+    }
+}
+
+fn lock_path(target: &Path) -> PathBuf {
+    let mut name = target.file_name().unwrap_or_default().to_os_string();
+    name.push(".lock");
+    target.with_file_name(name)
+}
+
+/// Replace `target` with `contents` so that a crash leaves either the old or the new file
+pub fn write_atomic(target: &Path, contents: &[u8]) -> io::Result<()> {
+    let dir = match target.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(dir)?;
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(target.file_name().unwrap_or_default());
+    temp_name.push(format!(".tmp-{}", std::process::id()));
+    let temp = dir.join(temp_name);
+
+    let written = (|| {
+        let mut file = File::create(&temp)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&temp, target)
+    })();
+    if written.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    written?;
+
+    // Make the rename itself durable; directories cannot be opened for syncing on Windows
+    #[cfg(unix)]
+    File::open(dir)?.sync_all()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_write_atomic_replaces_file_without_leftovers() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("config.json");
+        write_atomic(&target, b"old").unwrap();
+        write_atomic(&target, b"new").unwrap();
+
+        assert_eq!(fs::read(&target).unwrap(), b"new");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_lock_excludes_other_holders_until_dropped() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("config.json");
+
+        let lock = FileLock::acquire(&target).await.unwrap();
+        let error = FileLock::acquire_within(&target, Duration::ZERO)
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::WouldBlock);
+        drop(lock);
+
+        // The lock file stays in place so every process locks the same file
+        assert!(lock_path(&target).exists());
+        let _lock = FileLock::acquire_within(&target, Duration::ZERO)
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,458 @@
+//! Local proving benchmark and machine calibration
+//!
+//! `nexus-network bench` proves a standard set of fib inputs with 1..N parallel prover
+//! subprocesses and records throughput, latency and memory for each level. The result is
+//! cached next to the config so telemetry and `--max-difficulty auto` can rely on measured
+//! numbers instead of theoretical peak FLOPS.
+
+use crate::consts::cli_consts::{
+    AUTO_DIFFICULTY_LARGE_MIN_PPM, AUTO_DIFFICULTY_MEDIUM_MIN_PPM, BENCH_INPUTS,
+    BENCH_MAX_PARALLEL, BENCH_MEMORY_HEADROOM, BENCH_MIN_SPEEDUP, BENCH_RSS_SAMPLE_INTERVAL_MS,
+    PROJECTED_MEMORY_REQUIREMENT,
+};
+use crate::events::format_duration;
+use crate::nexus_orchestrator::TaskDifficulty;
+use crate::prover::engine::ProvingEngine;
+use crate::prover::types::ProverError;
+use crate::system;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tokio::sync::Semaphore;
+use tokio::task::{JoinHandle, JoinSet};
+
+/// Maximum task difficulty to request from the orchestrator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum MaxDifficulty {
+    Small,
+    Medium,
+    Large,
+    /// Pick a difficulty from the cached `nexus-network bench` calibration
+    Auto,
+}
+
+impl MaxDifficulty {
+    /// Resolve to a concrete difficulty. `Auto` falls back to `Large` without a calibration.
+    pub fn resolve(self, calibration: Option<&Calibration>) -> TaskDifficulty {
+        match self {
+            MaxDifficulty::Small => TaskDifficulty::Small,
+            MaxDifficulty::Medium => TaskDifficulty::Medium,
+            MaxDifficulty::Large => TaskDifficulty::Large,
+            MaxDifficulty::Auto => calibration
+                .map(Calibration::recommended_difficulty)
+                .unwrap_or(TaskDifficulty::Large),
+        }
+    }
+}
+
+/// Measurements for a single parallelism level
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelResult {
+    /// Number of prover subprocesses running at once
+    pub parallelism: usize,
+    /// Number of proofs that completed successfully
+    pub proofs: usize,
+    /// Number of proofs that failed
+    pub failures: usize,
+    /// Wall-clock time for the whole level
+    pub wall_time: Duration,
+    /// Successful proofs per minute of wall-clock time
+    pub proofs_per_minute: f64,
+    pub p50_latency: Duration,
+    pub p90_latency: Duration,
+    pub p99_latency: Duration,
+    /// Peak combined resident memory of the prover subprocesses, in bytes
+    pub peak_rss_bytes: u64,
+}
+
+/// Cached result of a benchmark run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Calibration {
+    /// Unix timestamp of the benchmark run
+    pub measured_at: u64,
+    /// CLI version that ran the benchmark
+    pub cli_version: String,
+    /// Work done by one benchmark proof, in GFLOP: the measured single-thread GFLOP/s of
+    /// this machine multiplied by the single-thread time per proof
+    pub gflop_per_proof: f64,
+    /// Results for each tested parallelism level, in ascending order
+    pub levels: Vec<LevelResult>,
+    /// Number of prover threads recommended for this machine
+    pub recommended_threads: usize,
+}
+
+impl Calibration {
+    /// Location of the calibration file, next to the config file.
+    pub fn path(config_path: &Path) -> PathBuf {
+        config_path.with_file_name("calibration.json")
+    }
+
+    /// Load a calibration from disk, if one exists and is readable.
+    pub fn load(path: &Path) -> Option<Self> {
+        let buf = fs::read(path).ok()?;
+        serde_json::from_slice(&buf).ok()
+    }
+
+    /// Save the calibration to disk, creating parent directories if needed.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        fs::write(path, json)
+    }
+
+    /// Results for the given parallelism, or for the highest tested level below it.
+    fn level(&self, parallelism: usize) -> Option<&LevelResult> {
+        self.levels
+            .iter()
+            .filter(|level| level.parallelism <= parallelism && level.proofs > 0)
+            .max_by_key(|level| level.parallelism)
+    }
+
+    /// Results for the recommended thread count.
+    pub fn recommended_level(&self) -> Option<&LevelResult> {
+        self.level(self.recommended_threads)
+    }
+
+    /// Effective GFLOP/s for `num_provers` threads, from the proof throughput measured at that
+    /// parallelism rather than assuming proving scales linearly with threads.
+    pub fn effective_gflops(&self, num_provers: usize) -> f64 {
+        self.level(num_provers).map_or(0.0, |level| {
+            self.gflop_per_proof * level.proofs_per_minute / 60.0
+        })
+    }
+
+    /// Largest task difficulty this machine can sustain at the recommended thread count.
+    pub fn recommended_difficulty(&self) -> TaskDifficulty {
+        let proofs_per_minute = self
+            .recommended_level()
+            .map_or(0.0, |level| level.proofs_per_minute);
+        if proofs_per_minute >= AUTO_DIFFICULTY_LARGE_MIN_PPM {
+            TaskDifficulty::Large
+        } else if proofs_per_minute >= AUTO_DIFFICULTY_MEDIUM_MIN_PPM {
+            TaskDifficulty::Medium
+        } else {
+            TaskDifficulty::Small
+        }
+    }
+
+    /// Print a per-level table and the recommendation.
+    pub fn print_summary(&self) {
+        println!(
+            "{:>8} {:>8} {:>10} {:>9} {:>9} {:>9} {:>10}",
+            "threads", "proofs", "proofs/min", "p50", "p90", "p99", "peak RSS"
+        );
+        for level in &self.levels {
+            println!(
+                "{:>8} {:>8} {:>10.2} {:>9} {:>9} {:>9} {:>7.2} GB",
+                level.parallelism,
+                format!("{}/{}", level.proofs, level.proofs + level.failures),
+                level.proofs_per_minute,
+                format_duration(level.p50_latency),
+                format_duration(level.p90_latency),
+                format_duration(level.p99_latency),
+                level.peak_rss_bytes as f64 / 1024.0 / 1024.0 / 1024.0,
+            );
+        }
+        crate::print_cmd_success!(
+            "Recommendation",
+            "Run with `--max-threads {}` ({:.2} GFLOP/s effective, {} tasks with `--max-difficulty auto`)",
+            self.recommended_threads,
+            self.effective_gflops(self.recommended_threads),
+            self.recommended_difficulty().as_str_name().to_lowercase()
+        );
+    }
+}
+
+/// Effective GFLOP/s for `num_provers` threads, from the calibration if there is one,
+/// otherwise from the theoretical peak.
+pub fn effective_gflops(calibration: Option<&Calibration>, num_provers: usize) -> f64 {
+    match calibration {
+        Some(calibration) => calibration.effective_gflops(num_provers),
+        None => system::estimate_peak_gflops(num_provers),
+    }
+}
+
+/// Default upper bound on parallelism: limited by cores, memory and the worker thread cap.
+pub fn default_max_parallel() -> usize {
+    let total_memory = system::total_memory_gb() * 1024.0 * 1024.0 * 1024.0;
+    let by_memory = (total_memory / PROJECTED_MEMORY_REQUIREMENT as f64) as usize;
+    system::num_cores()
+        .min(by_memory)
+        .clamp(1, BENCH_MAX_PARALLEL)
+}
+
+/// Prove the standard inputs at each parallelism level from 1 to `max_parallel`.
+///
+/// Each level proves every input `rounds` times per prover. Fails only if no proof succeeds
+/// at a single thread, since nothing meaningful can be recommended in that case.
+pub async fn run_benchmark(max_parallel: usize, rounds: usize) -> Result<Calibration, ProverError> {
+    let mut levels = Vec::with_capacity(max_parallel);
+    for parallelism in 1..=max_parallel {
+        crate::print_cmd_info!(
+            "Benchmarking",
+            "{} parallel prover(s), {} proofs...",
+            parallelism,
+            parallelism * rounds * BENCH_INPUTS.len()
+        );
+        let (level, first_error) = run_level(parallelism, rounds).await;
+        if level.proofs == 0 {
+            if let Some(e) = first_error.filter(|_| parallelism == 1) {
+                return Err(e);
+            }
+            crate::print_cmd_warn!(
+                "Benchmark stopped",
+                "All proofs failed with {} parallel provers",
+                parallelism
+            );
+            levels.push(level);
+            break;
+        }
+        levels.push(level);
+    }
+
+    let total_memory_bytes = (system::total_memory_gb() * 1024.0 * 1024.0 * 1024.0) as u64;
+    let gflop_per_proof = levels
+        .first()
+        .filter(|level| level.proofs_per_minute > 0.0)
+        .map_or(0.0, |level| {
+            system::measure_gflops() as f64 * 60.0 / level.proofs_per_minute
+        });
+    Ok(Calibration {
+        measured_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        cli_version: env!("CARGO_PKG_VERSION").to_string(),
+        gflop_per_proof,
+        recommended_threads: recommend_threads(&levels, total_memory_bytes),
+        levels,
+    })
+}
+
+/// Run one parallelism level, returning its results and the first proving error, if any.
+async fn run_level(parallelism: usize, rounds: usize) -> (LevelResult, Option<ProverError>) {
+    let stop = Arc::new(AtomicBool::new(false));
+    let sampler = spawn_rss_sampler(stop.clone());
+
+    let semaphore = Arc::new(Semaphore::new(parallelism));
+    let mut provers = JoinSet::new();
+    let start = Instant::now();
+    for inputs in BENCH_INPUTS
+        .iter()
+        .cycle()
+        .take(parallelism * rounds * BENCH_INPUTS.len())
+    {
+        let permit = semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("benchmark semaphore is never closed");
+        let inputs = *inputs;
+        provers.spawn(async move {
+            let _permit = permit;
+            let proof_start = Instant::now();
+            let output = ProvingEngine::run_prover_subprocess(&inputs, None).await?;
+            ProvingEngine::proof_from_subprocess_output(&output)?;
+            Ok::<Duration, ProverError>(proof_start.elapsed())
+        });
+    }
+
+    let mut latencies = Vec::new();
+    let mut failures = 0;
+    let mut first_error = None;
+    while let Some(result) = provers.join_next().await {
+        match result {
+            Ok(Ok(latency)) => latencies.push(latency),
+            Ok(Err(e)) => {
+                failures += 1;
+                first_error.get_or_insert(e);
+            }
+            Err(_) => failures += 1,
+        }
+    }
+    let wall_time = start.elapsed();
+
+    stop.store(true, Ordering::Relaxed);
+    let peak_rss_bytes = sampler.await.unwrap_or(0);
+
+    latencies.sort();
+    let proofs_per_minute = if wall_time.is_zero() {
+        0.0
+    } else {
+        latencies.len() as f64 * 60.0 / wall_time.as_secs_f64()
+    };
+    let level = LevelResult {
+        parallelism,
+        proofs: latencies.len(),
+        failures,
+        wall_time,
+        proofs_per_minute,
+        p50_latency: percentile(&latencies, 50.0),
+        p90_latency: percentile(&latencies, 90.0),
+        p99_latency: percentile(&latencies, 99.0),
+        peak_rss_bytes,
+    };
+    (level, first_error)
+}
+
+/// Track the peak combined memory of this process's children until `stop` is set.
+fn spawn_rss_sampler(stop: Arc<AtomicBool>) -> JoinHandle<u64> {
+    tokio::spawn(async move {
+        let current_pid = Pid::from(std::process::id() as usize);
+        let mut sysinfo = System::new();
+        let mut peak = 0;
+        while !stop.load(Ordering::Relaxed) {
+            sysinfo.refresh_processes_specifics(
+                ProcessesToUpdate::All,
+                true,
+                ProcessRefreshKind::nothing().with_memory(),
+            );
+            let rss: u64 = sysinfo
+                .processes()
+                .values()
+                .filter(|process| process.parent() == Some(current_pid))
+                .map(|process| process.memory())
+                .sum();
+            peak = peak.max(rss);
+            tokio::time::sleep(Duration::from_millis(BENCH_RSS_SAMPLE_INTERVAL_MS)).await;
+        }
+        peak
+    })
+}
+
+/// Nearest-rank percentile of an ascending list of durations.
+pub fn percentile(sorted: &[Duration], pct: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (pct / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Pick the thread count to recommend: keep adding threads while every proof succeeds,
+/// memory stays within headroom and throughput improves by at least `BENCH_MIN_SPEEDUP`.
+pub fn recommend_threads(levels: &[LevelResult], total_memory_bytes: u64) -> usize {
+    let memory_limit = total_memory_bytes as f64 * BENCH_MEMORY_HEADROOM;
+    let mut best: Option<&LevelResult> = None;
+    for level in levels {
+        if level.proofs == 0 || level.failures > 0 || level.peak_rss_bytes as f64 > memory_limit {
+            break;
+        }
+        match best {
+            Some(current)
+                if level.proofs_per_minute < current.proofs_per_minute * BENCH_MIN_SPEEDUP =>
+            {
+                break;
+            }
+            _ => best = Some(level),
+        }
+    }
+    best.map_or(1, |level| level.parallelism)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    fn level(parallelism: usize, proofs_per_minute: f64, peak_rss_gb: u64) -> LevelResult {
+        LevelResult {
+            parallelism,
+            proofs: 3 * parallelism,
+            failures: 0,
+            wall_time: Duration::from_secs(60),
+            proofs_per_minute,
+            p50_latency: Duration::from_secs(10),
+            p90_latency: Duration::from_secs(12),
+            p99_latency: Duration::from_secs(15),
+            peak_rss_bytes: peak_rss_gb * GB,
+        }
+    }
+
+    fn calibration(levels: Vec<LevelResult>, recommended_threads: usize) -> Calibration {
+        Calibration {
+            measured_at: 0,
+            cli_version: "0.0.0".to_string(),
+            gflop_per_proof: 300.0,
+            levels,
+            recommended_threads,
+        }
+    }
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        let sorted: Vec<Duration> = (1..=10).map(Duration::from_secs).collect();
+        assert_eq!(percentile(&sorted, 50.0), Duration::from_secs(5));
+        assert_eq!(percentile(&sorted, 90.0), Duration::from_secs(9));
+        assert_eq!(percentile(&sorted, 99.0), Duration::from_secs(10));
+        assert_eq!(percentile(&sorted[..1], 50.0), Duration::from_secs(1));
+        assert_eq!(percentile(&[], 50.0), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_recommend_threads_stops_when_scaling_flattens() {
+        let levels = vec![level(1, 2.0, 4), level(2, 3.8, 8), level(3, 4.0, 12)];
+        assert_eq!(recommend_threads(&levels, 64 * GB), 2);
+    }
+
+    #[test]
+    fn test_recommend_threads_respects_memory_and_failures() {
+        let levels = vec![level(1, 2.0, 4), level(2, 4.0, 8), level(3, 6.0, 15)];
+        assert_eq!(recommend_threads(&levels, 16 * GB), 2);
+
+        let mut failing = level(2, 4.0, 8);
+        failing.failures = 1;
+        assert_eq!(recommend_threads(&[level(1, 2.0, 4), failing], 64 * GB), 1);
+        assert_eq!(recommend_threads(&[], 64 * GB), 1);
+    }
+
+    #[test]
+    fn test_effective_gflops_follows_measured_throughput() {
+        let calibration = calibration(vec![level(1, 2.0, 4), level(2, 3.0, 8)], 2);
+        assert_eq!(calibration.effective_gflops(1), 10.0);
+        assert_eq!(calibration.effective_gflops(2), 15.0);
+        // Beyond the tested range, the highest measured level is used
+        assert_eq!(calibration.effective_gflops(8), 15.0);
+        assert_eq!(effective_gflops(Some(&calibration), 2), 15.0);
+    }
+
+    #[test]
+    fn test_max_difficulty_resolution() {
+        let slow = calibration(vec![level(1, 1.0, 4)], 1);
+        let fast = calibration(vec![level(1, 4.0, 4), level(2, 7.0, 8)], 2);
+        assert_eq!(
+            MaxDifficulty::Auto.resolve(Some(&slow)),
+            TaskDifficulty::Small
+        );
+        assert_eq!(
+            MaxDifficulty::Auto.resolve(Some(&fast)),
+            TaskDifficulty::Large
+        );
+        assert_eq!(MaxDifficulty::Auto.resolve(None), TaskDifficulty::Large);
+        assert_eq!(
+            MaxDifficulty::Medium.resolve(Some(&fast)),
+            TaskDifficulty::Medium
+        );
+    }
+
+    #[test]
+    fn test_calibration_roundtrip() {
+        let dir = tempdir().unwrap();
+        let path = Calibration::path(&dir.path().join("config.json"));
+        assert!(Calibration::load(&path).is_none());
+
+        let calibration = calibration(vec![level(1, 2.0, 4)], 1);
+        calibration.save(&path).unwrap();
+        assert_eq!(Calibration::load(&path), Some(calibration));
+    }
+}
@@ -1,24 +1,78 @@
 //! Application configuration.
 
+use crate::atomic_write::{FileLock, write_atomic};
 use crate::cli_messages::{print_error, print_info, print_success};
+use crate::consts::cli_consts::CONFIG_VERSION;
 use crate::environment::Environment;
+use crate::event_bus::SinkConfig;
+use crate::keys;
+use crate::network::{Operation, RetryPolicy, retry};
 use crate::orchestrator::Orchestrator;
+use crate::orchestrator::error::ServerError;
+use crate::schedule::ScheduleConfig;
+use crate::thermal::ThermalConfig;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::error::Error;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Once, OnceLock};
 
-/// Get the path to the Nexus config file, typically located at ~/.nexus/config.json.
+/// Name of the config file within a config directory
+pub const CONFIG_FILE_NAME: &str = "config.json";
+
+/// Config file selected with `--config`, which takes precedence over the directory defaults
+static SELECTED_CONFIG_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use `path` as the config file for the rest of the run. The node's other state, such as
+/// the calibration and the version requirements cache, is kept in the same directory.
+pub fn select_config_path(path: PathBuf) {
+    let _ = SELECTED_CONFIG_PATH.set(path);
+}
+
+/// Get the path to the Nexus config file.
+///
+/// In order of precedence: the file selected with `--config`, `config.json` in
+/// `NEXUS_CONFIG_DIR`, `~/.nexus/config.json` if that directory exists, `nexus/config.json`
+/// in `XDG_CONFIG_HOME` if that is set, and finally `~/.nexus/config.json`.
 pub fn get_config_path() -> Result<PathBuf, std::io::Error> {
-    let home_path = home::home_dir().ok_or(std::io::Error::new(
+    if let Some(path) = SELECTED_CONFIG_PATH.get() {
+        return Ok(path.clone());
+    }
+    let env_dir = |name: &str| {
+        std::env::var_os(name)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+    let dir = default_config_dir(
+        env_dir("NEXUS_CONFIG_DIR"),
+        env_dir("XDG_CONFIG_HOME"),
+        home::home_dir(),
+    )
+    .ok_or(std::io::Error::new(
         std::io::ErrorKind::NotFound,
-        "Home directory not found",
+        "Home directory not found; set NEXUS_CONFIG_DIR or use --config",
     ))?;
-    let config_path = home_path.join(".nexus").join("config.json");
-    Ok(config_path)
+    Ok(dir.join(CONFIG_FILE_NAME))
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+fn default_config_dir(
+    nexus_config_dir: Option<PathBuf>,
+    xdg_config_home: Option<PathBuf>,
+    home: Option<PathBuf>,
+) -> Option<PathBuf> {
+    if nexus_config_dir.is_some() {
+        return nexus_config_dir;
+    }
+    // Existing installs keep their config where it is
+    let legacy = home.map(|home| home.join(".nexus"));
+    if legacy.as_ref().is_some_and(|legacy| legacy.is_dir()) {
+        return legacy;
+    }
+    xdg_config_home.map(|xdg| xdg.join("nexus")).or(legacy)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct Config {
     /// Environment from config file
     #[serde(default)]
@@ -35,6 +89,23 @@ pub struct Config {
     /// Node ID, resolved to a valid u64 during `Config::resolve`
     #[serde(default)]
     pub node_id: String,
+
+    /// Additional destinations for worker events, such as a log file or webhook
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub event_sinks: Vec<SinkConfig>,
+
+    /// When the node may fetch new tasks
+    #[serde(default, skip_serializing_if = "ScheduleConfig::is_unrestricted")]
+    pub schedule: ScheduleConfig,
+
+    /// When proving backs off to let the machine cool down
+    #[serde(default, skip_serializing_if = "ThermalConfig::is_unrestricted")]
+    pub thermal: ThermalConfig,
+
+    /// Fields this version does not know, such as those added by a newer release, kept so
+    /// that saving does not drop them
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 impl Config {
@@ -50,43 +121,143 @@ impl Config {
             wallet_address,
             node_id,
             environment: environment.to_string(),
+            event_sinks: Vec::new(),
+            schedule: ScheduleConfig::default(),
+            thermal: ThermalConfig::default(),
+            extra: Map::new(),
         }
     }
 
-    /// Loads configuration from a JSON file at the given path.
+    /// Loads configuration from a JSON file at the given path, migrating older layouts.
+    ///
+    /// A file written by a newer release is read as is, with a warning, but
+    /// [`Config::update`] refuses to change it.
     pub fn load_from_file(path: &Path) -> Result<Self, std::io::Error> {
+        let (config, version) = Self::read(path)?;
+        if version > CONFIG_VERSION {
+            static WARNED: Once = Once::new();
+            WARNED.call_once(|| {
+                crate::print_cmd_warn!(
+                    "Newer config file",
+                    "{} was written by a newer release (config version {}, this release writes {}); it is used read-only. Update nexus-network to change it.",
+                    path.display(),
+                    version,
+                    CONFIG_VERSION
+                );
+            });
+        }
+        Ok(config)
+    }
+
+    /// Reads the config at `path` along with the layout version it was written with.
+    fn read(path: &Path) -> Result<(Self, u32), std::io::Error> {
+        let invalid = |msg: String| std::io::Error::new(std::io::ErrorKind::InvalidData, msg);
         let buf = fs::read(path)?;
-        let config: Config = serde_json::from_slice(&buf)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let value: Value = serde_json::from_slice(&buf).map_err(|e| {
+            invalid(format!(
+                "{} is not valid JSON ({}); it may be damaged, so fix or remove it",
+                path.display(),
+                e
+            ))
+        })?;
+        let (value, version) =
+            migrate(value).map_err(|e| invalid(format!("{}: {}", path.display(), e)))?;
+        let config = serde_json::from_value(value).map_err(|e| {
+            invalid(format!(
+                "{} has an unexpected layout: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Ok((config, version))
+    }
+
+    /// Loads the config at `path`, applies `change` and saves it, holding the file's lock
+    /// throughout so that concurrent updates by other processes are not lost. The file is
+    /// replaced atomically, so a crash never leaves a damaged file behind. A missing file
+    /// starts out as the default config. A file written by a newer release is left untouched
+    /// and an error returned, as saving it would drop what this release does not understand.
+    pub async fn update(
+        path: &Path,
+        change: impl FnOnce(&mut Config),
+    ) -> Result<Self, std::io::Error> {
+        let _lock = FileLock::acquire(path).await?;
+        let mut config = if path.exists() {
+            let (config, version) = Self::read(path)?;
+            if version > CONFIG_VERSION {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!(
+                        "{} was written by a newer release (config version {}, this release writes {}); update nexus-network to change it",
+                        path.display(),
+                        version,
+                        CONFIG_VERSION
+                    ),
+                ));
+            }
+            config
+        } else {
+            Config::default()
+        };
+        change(&mut config);
+        config.write(path)?;
         Ok(config)
     }
 
-    /// Saves the configuration to a JSON file at the given path.
-    pub fn save(&self, path: &Path) -> Result<(), std::io::Error> {
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        let json = serde_json::to_string_pretty(self).map_err(|e| {
+    fn write(&self, path: &Path) -> Result<(), std::io::Error> {
+        let serialization_failed = |e: serde_json::Error| {
             std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
                 format!("Serialization failed: {}", e),
             )
-        })?;
-        fs::write(path, json)?;
-        Ok(())
+        };
+        let mut value = serde_json::to_value(self).map_err(serialization_failed)?;
+        if let Value::Object(fields) = &mut value {
+            fields.insert(VERSION_FIELD.to_string(), CONFIG_VERSION.into());
+        }
+        let json = serde_json::to_string_pretty(&value).map_err(serialization_failed)?;
+        write_atomic(path, json.as_bytes())
     }
 
-    /// Clear the node ID configuration file.
+    /// Clear the node configuration file at `path`, leaving other nodes' configs and the
+    /// state shared by the directory in place.
+    ///
+    /// A file named `config.json` is always removed. Any other `.json` file is only removed
+    /// if it is a node config, or too damaged to tell: a node config holds its layout
+    /// version, a user ID or a node ID, which the other JSON files in the config directory,
+    /// such as the calibration, never do.
     pub fn clear_node_config(path: &Path) -> std::io::Result<()> {
         if !path.exists() {
             println!("No config file found at {}", path.display());
             return Ok(());
         }
-        if !path.ends_with("config.json") {
-            return Err(std::io::Error::new(
+        if path
+            .file_name()
+            .is_some_and(|name| name == CONFIG_FILE_NAME)
+        {
+            return fs::remove_file(path);
+        }
+        let not_a_config = |reason: &str| {
+            std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
-                "Path must end with config.json",
-            ));
+                format!("{} is not a node config file: {}", path.display(), reason),
+            )
+        };
+        if path.extension().is_none_or(|extension| extension != "json") {
+            return Err(not_a_config("its name does not end with .json"));
+        }
+        // A damaged config is removed too, as logging out is how to start over from it
+        if let Ok(value) = serde_json::from_slice::<Value>(&fs::read(path)?) {
+            let is_config = value.as_object().is_some_and(|fields| {
+                [VERSION_FIELD, "user_id", "node_id"]
+                    .iter()
+                    .any(|field| fields.contains_key(*field))
+            });
+            if !is_config {
+                return Err(not_a_config(
+                    "it holds no config version, user ID or node ID",
+                ));
+            }
         }
         fs::remove_file(path)
     }
@@ -102,14 +273,20 @@ impl Config {
             print_success("Using provided Node ID", &format!("Node ID: {}", node_id));
 
             // Get the wallet address for analytics
-            let wallet_address = orchestrator.get_node(&node_id.to_string()).await?;
+            let wallet_address = lookup_wallet_address(orchestrator, node_id).await?;
 
-            // Create a minimal config with the provided node_id
+            // Create a minimal config with the provided node_id. Sinks, the schedule and
+            // thermal limits still apply when the node ID comes from the command line.
+            let saved = Config::load_from_file(config_path).unwrap_or_default();
             let config = Config {
                 user_id: "anonymous".to_string(), // Use anonymous for --node-id shortcut
                 wallet_address,
                 node_id: node_id.to_string(),
                 environment: "".to_string(),
+                event_sinks: saved.event_sinks,
+                schedule: saved.schedule,
+                thermal: saved.thermal,
+                extra: saved.extra,
             };
 
             return Ok(config);
@@ -140,14 +317,14 @@ impl Config {
                 // The config is present but incomplete or invalid
                 print_error(
                     "Your configuration is incomplete or invalid.",
-                    Some("Please register your node. Start with: nexus-cli register-node"),
+                    Some(&e.to_string()),
                 );
                 return Err(e);
             }
         };
 
         // Get the wallet address for analytics
-        let wallet_address = orchestrator.get_node(&resolved_node_id.to_string()).await?;
+        let wallet_address = lookup_wallet_address(orchestrator, resolved_node_id).await?;
 
         // Populate the config struct with the resolved values
         config.node_id = resolved_node_id.to_string();
@@ -157,16 +334,12 @@ impl Config {
     }
 
     /// Resolves node ID from the configuration file content
-    fn resolve_node_id_from_config(&self) -> Result<u64, Box<dyn Error>> {
+    pub(crate) fn resolve_node_id_from_config(&self) -> Result<u64, Box<dyn Error>> {
         if self.user_id.is_empty() {
             return Err("User not registered in config file.".into());
         }
 
         if self.node_id.is_empty() {
-            print_error(
-                "User registered, but no node found",
-                Some("Please register a node to continue: nexus-cli register-node"),
-            );
             return Err(
                 "Node registration required. Please run 'nexus-cli register-node' first.".into(),
             );
@@ -174,16 +347,78 @@ impl Config {
 
         match self.node_id.parse::<u64>() {
             Ok(id) => Ok(id),
-            Err(_) => {
+            Err(_) => Err(
+                "Invalid node ID in config. Please run 'nexus-cli register-node' to fix this."
+                    .into(),
+            ),
+        }
+    }
+}
+
+/// Look up the wallet address linked to a node, explaining an unknown node ID to the user.
+async fn lookup_wallet_address(
+    orchestrator: &impl Orchestrator,
+    node_id: u64,
+) -> Result<String, Box<dyn Error>> {
+    let node_id_str = node_id.to_string();
+    let result = retry(&RetryPolicy::for_operation(Operation::GetNode), || {
+        orchestrator.get_node(&node_id_str)
+    })
+    .await;
+    // Saved in the same checksummed form as a registered wallet
+    result
+        .map(|wallet| keys::parse_eth_address(&wallet).unwrap_or(wallet))
+        .map_err(|e| {
+            if e.server_error().is_some_and(ServerError::is_not_found) {
                 print_error(
-                    "Invalid node ID in config file",
-                    Some("Please register a new node: nexus-cli register-node"),
+                    &format!("Node ID {} is not known to the orchestrator.", node_id),
+                    Some("Check the node ID, or register a new node: nexus-cli register-node"),
                 );
-                Err(
-                    "Invalid node ID in config. Please run 'nexus-cli register-node' to fix this."
-                        .into(),
-                )
             }
+            e.into()
+        })
+}
+
+/// Field holding the layout version of a config file. Files without one are version 1.
+const VERSION_FIELD: &str = "version";
+
+/// Upgrade a config file from the layout of its version to [`CONFIG_VERSION`], one step at
+/// a time. Step `n` upgrades version `n + 1`. Files from newer releases are left as they are.
+const MIGRATIONS: [fn(&mut Map<String, Value>); CONFIG_VERSION as usize - 1] = [migrate_v1];
+
+fn migrate(value: Value) -> Result<(Value, u32), String> {
+    let Value::Object(mut fields) = value else {
+        return Err("expected a JSON object".to_string());
+    };
+    let version = match fields.remove(VERSION_FIELD) {
+        None => 1,
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .filter(|version| *version >= 1)
+            .ok_or_else(|| format!("invalid config version {}", version))?,
+    };
+    if version <= CONFIG_VERSION {
+        for migration in &MIGRATIONS[version as usize - 1..] {
+            migration(&mut fields);
+        }
+    }
+    Ok((Value::Object(fields), version))
+}
+
+/// Version 1 files were written by hand or by early releases, which stored the node ID as a
+/// number and missing values as null.
+fn migrate_v1(fields: &mut Map<String, Value>) {
+    for key in ["environment", "user_id", "wallet_address", "node_id"] {
+        match fields.get(key) {
+            Some(Value::Number(number)) => {
+                let text = number.to_string();
+                fields.insert(key.to_string(), Value::String(text));
+            }
+            Some(Value::Null) => {
+                fields.remove(key);
+            }
+            _ => {}
         }
     }
 }
@@ -202,29 +437,38 @@ mod tests {
             user_id: "test_user_id".to_string(),
             wallet_address: "0x1234567890abcdef1234567890abcdef12345678".to_string(),
             node_id: "test_node_id".to_string(),
+            event_sinks: Vec::new(),
+            schedule: ScheduleConfig::default(),
+            thermal: ThermalConfig::default(),
+            extra: Map::new(),
         }
     }
 
-    #[test]
+    /// Replace the config file at `path` with `config`.
+    async fn save(config: &Config, path: &Path) -> Result<Config, std::io::Error> {
+        Config::update(path, |saved| *saved = config.clone()).await
+    }
+
+    #[tokio::test]
     // Loading a saved configuration file should return the same configuration.
-    fn test_load_recovers_saved_config() {
+    async fn test_load_recovers_saved_config() {
         let dir = tempdir().unwrap();
         let path = dir.path().join("config.json");
 
         let config = get_config();
-        config.save(&path).unwrap();
+        save(&config, &path).await.unwrap();
 
         let loaded_config = Config::load_from_file(&path).unwrap();
         assert_eq!(config, loaded_config);
     }
 
-    #[test]
+    #[tokio::test]
     // Saving a configuration should create directories if they don't exist.
-    fn test_save_creates_directories() {
+    async fn test_save_creates_directories() {
         let dir = tempdir().unwrap();
         let path = dir.path().join("nonexistent_dir").join("config.json");
         let config = get_config();
-        let result = config.save(&path);
+        let result = save(&config, &path).await;
 
         // Check if the directories were created
         assert!(result.is_ok(), "Failed to save config");
@@ -234,27 +478,46 @@ mod tests {
         );
     }
 
-    #[test]
+    #[tokio::test]
     // Saving a configuration should overwrite an existing file.
-    fn test_save_overwrites_existing_file() {
+    async fn test_save_overwrites_existing_file() {
         let dir = tempdir().unwrap();
         let path = dir.path().join("config.json");
 
         // Create an initial config and save it
         let mut config1 = get_config();
         config1.user_id = "test_user_id".to_string();
-        config1.save(&path).unwrap();
+        save(&config1, &path).await.unwrap();
 
         // Create a new config and save it to the same path
         let mut config2 = get_config();
         config2.user_id = "new_test_user_id".to_string();
-        config2.save(&path).unwrap();
+        save(&config2, &path).await.unwrap();
 
         // Load the saved config and check if it matches the second one
         let loaded_config = Config::load_from_file(&path).unwrap();
         assert_eq!(config2, loaded_config);
     }
 
+    #[tokio::test]
+    // Updating a config should keep the changes saved since it was last read.
+    async fn test_update_keeps_concurrent_changes() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        save(&get_config(), &path).await.unwrap();
+
+        let mut other = Config::load_from_file(&path).unwrap();
+        other.thermal.max_cpu_temp_c = Some(80.0);
+        save(&other, &path).await.unwrap();
+
+        let updated = Config::update(&path, |config| config.node_id = "42".to_string())
+            .await
+            .unwrap();
+        assert_eq!(updated.node_id, "42");
+        assert_eq!(updated.thermal.max_cpu_temp_c, Some(80.0));
+        assert_eq!(Config::load_from_file(&path).unwrap(), updated);
+    }
+
     #[test]
     // Loading an invalid JSON file should return an error.
     fn test_load_rejects_invalid_json() {
@@ -268,18 +531,91 @@ mod tests {
         assert!(result.is_err());
     }
 
-    #[test]
+    #[tokio::test]
     // Clearing the node configuration file should remove it if it exists.
-    fn test_clear_node_config_removes_file() {
+    async fn test_clear_node_config_removes_file() {
         let dir = tempdir().unwrap();
         let path = dir.path().join("config.json");
         let config = get_config();
-        config.save(&path).unwrap();
+        save(&config, &path).await.unwrap();
 
         Config::clear_node_config(&path).unwrap();
         assert!(!path.exists(), "Config file was not removed");
     }
 
+    #[tokio::test]
+    // Clearing a node configuration should leave other JSON state files in place.
+    async fn test_clear_node_config_only_removes_node_configs() {
+        let dir = tempdir().unwrap();
+        let node = dir.path().join("node-2.json");
+        save(&get_config(), &node).await.unwrap();
+        let calibration = dir.path().join("calibration.json");
+        fs::write(
+            &calibration,
+            r#"{ "measured_at": 1700000000, "cli_version": "0.10.0", "gflop_per_proof": 90.0, "levels": [], "recommended_threads": 2 }"#,
+        )
+        .unwrap();
+        let requirements = dir.path().join("version_requirements.json");
+        fs::write(&requirements, r#"{ "version_constraints": [] }"#).unwrap();
+        let not_json = dir.path().join("config.txt");
+        fs::write(&not_json, r#"{ "user_id": "test_user" }"#).unwrap();
+
+        for other in [&calibration, &requirements, &not_json] {
+            assert!(Config::clear_node_config(other).is_err());
+            assert!(other.exists());
+        }
+        Config::clear_node_config(&node).unwrap();
+        assert!(!node.exists());
+    }
+
+    #[test]
+    // Clearing a damaged configuration file should remove it, so that the node can start over.
+    fn test_clear_node_config_removes_damaged_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        fs::write(&path, r#"{ "user_id": "test_"#).unwrap();
+        Config::clear_node_config(&path).unwrap();
+        assert!(!path.exists(), "Damaged config file was not removed");
+
+        let node = dir.path().join("node-2.json");
+        fs::write(&node, "[1, 2,").unwrap();
+        Config::clear_node_config(&node).unwrap();
+        assert!(!node.exists(), "Damaged node config file was not removed");
+    }
+
+    #[test]
+    // The config directory should follow NEXUS_CONFIG_DIR, an existing ~/.nexus, then XDG.
+    fn test_default_config_dir_precedence() {
+        let dir = tempdir().unwrap();
+        let home = dir.path().join("home");
+        let xdg = dir.path().join("xdg");
+        let explicit = dir.path().join("explicit");
+
+        assert_eq!(
+            default_config_dir(
+                Some(explicit.clone()),
+                Some(xdg.clone()),
+                Some(home.clone())
+            ),
+            Some(explicit)
+        );
+        assert_eq!(
+            default_config_dir(None, Some(xdg.clone()), Some(home.clone())),
+            Some(xdg.join("nexus"))
+        );
+        assert_eq!(
+            default_config_dir(None, None, Some(home.clone())),
+            Some(home.join(".nexus"))
+        );
+        // An existing ~/.nexus wins over XDG so current installs keep their config
+        fs::create_dir_all(home.join(".nexus")).unwrap();
+        assert_eq!(
+            default_config_dir(None, Some(xdg), Some(home.clone())),
+            Some(home.join(".nexus"))
+        );
+        assert_eq!(default_config_dir(None, None, None), None);
+    }
+
     #[test]
     // Should load JSON containing a user_id and empty strings for other fields.
     fn test_load_config_with_user_id_and_empty_fields() {
@@ -330,9 +666,9 @@ mod tests {
         }
     }
 
-    #[test]
+    #[tokio::test]
     // (Backwards compatibility) Should load JSON with node_id and empty strings for other fields.
-    fn test_load_config_with_node_id_and_empty_strings() {
+    async fn test_load_config_with_node_id_and_empty_strings() {
         let dir = tempdir().unwrap();
         let path = dir.path().join("config.json");
 
@@ -341,8 +677,12 @@ mod tests {
             user_id: "".to_string(),
             wallet_address: "".to_string(),
             node_id: "12345".to_string(),
+            event_sinks: Vec::new(),
+            schedule: ScheduleConfig::default(),
+            thermal: ThermalConfig::default(),
+            extra: Map::new(),
         };
-        config.save(&path).unwrap();
+        save(&config, &path).await.unwrap();
 
         match Config::load_from_file(&path) {
             Ok(config) => {
@@ -359,6 +699,44 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    // Unversioned files are migrated, and fields unknown to this release survive a save.
+    async fn test_load_migrates_and_preserves_unknown_fields() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        fs::write(
+            &path,
+            r#"{ "node_id": 12345, "user_id": null, "future_setting": {"enabled": true} }"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from_file(&path).unwrap();
+        assert_eq!(config.node_id, "12345");
+        assert!(config.user_id.is_empty());
+        save(&config, &path).await.unwrap();
+
+        let saved: Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!(saved[VERSION_FIELD], CONFIG_VERSION);
+        assert_eq!(saved["future_setting"]["enabled"], true);
+        assert_eq!(Config::load_from_file(&path).unwrap(), config);
+    }
+
+    #[tokio::test]
+    // A file from a newer release is loaded, but updating it must leave it untouched.
+    async fn test_newer_version_is_read_only() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        let contents = format!(r#"{{ "version": {}, "node_id": "1" }}"#, CONFIG_VERSION + 1);
+        fs::write(&path, &contents).unwrap();
+
+        assert_eq!(Config::load_from_file(&path).unwrap().node_id, "1");
+        let error = Config::update(&path, |config| config.node_id = "2".to_string())
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("newer release"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), contents);
+    }
+
     #[test]
     // Should ignore unexpected fields in the JSON.
     fn test_load_config_with_additional_fields() {
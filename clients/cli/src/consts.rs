@@ -16,6 +16,9 @@ pub mod cli_consts {
     /// Maximum number of event buffer size for worker threads
     pub const EVENT_QUEUE_SIZE: usize = 100;
 
+    /// Number of events the event bus buffers for each sink before a slow sink skips events
+    pub const EVENT_BUS_CAPACITY: usize = 1000;
+
     // =============================================================================
     // PROVING CONFIGURATIONS
     // =============================================================================
@@ -26,9 +29,102 @@ pub mod cli_consts {
     /// Subprocess error code indicating an internal failure of the proving
     pub const SUBPROCESS_INTERNAL_ERROR_CODE: i32 = 3;
 
+    /// Number of trailing prover subprocess stderr lines kept with a failed task
+    pub const SUBPROCESS_STDERR_TAIL_LINES: usize = 20;
+
+    /// Number of those stderr lines shown under a failure in the dashboard log
+    pub const SUBPROCESS_STDERR_DISPLAY_LINES: usize = 3;
+
+    /// Number of proofs a persistent prover child generates before it is replaced
+    pub const PROVER_CHILD_MAX_PROOFS: u32 = 50;
+
+    /// Growth in prover child memory, measured from after its first proof, at which it is replaced
+    pub const PROVER_CHILD_MAX_RSS_GROWTH: u64 = 1024 * 1024 * 1024; // 1gb
+
+    /// Largest request or response frame accepted from the other side of the prover child pipe
+    pub const PROVER_CHILD_MAX_FRAME_BYTES: u32 = 256 * 1024 * 1024;
+
+    /// Time a prover child is given to exit after its input is closed before it is killed
+    pub const PROVER_CHILD_EXIT_TIMEOUT_SECS: u64 = 5;
+
     /// "Reasonable" generic projection task memory requirement.
     pub const PROJECTED_MEMORY_REQUIREMENT: u64 = 4294967296; // 4gb
 
+    /// Most inputs of one task proven at once, each in its own prover child
+    pub const PROVER_MAX_SLOTS: usize = 4;
+
+    /// Fraction of available memory that concurrently proven inputs may use
+    pub const PROVER_SLOT_MEMORY_HEADROOM: f64 = 0.8;
+
+    /// Number of recent inputs averaged when estimating time remaining for a task
+    pub const PROGRESS_ROLLING_WINDOW: usize = 5;
+
+    /// Interval at which a paused worker checks whether its schedule allows proving again
+    pub const SCHEDULE_POLL_SECS: u64 = 30;
+
+    /// Default length of a cool-down pause while the machine is too hot
+    pub const THERMAL_COOLDOWN_SECS: u64 = 60;
+
+    /// Degrees below the temperature limit at which inputs are proven one at a time
+    pub const THERMAL_TEMP_MARGIN_C: f32 = 5.0;
+
+    /// Load per core below the load limit at which inputs are proven one at a time
+    pub const THERMAL_LOAD_MARGIN: f32 = 0.25;
+
+    // =============================================================================
+    // BENCHMARK CONFIGURATION
+    // =============================================================================
+
+    /// Standard fib inputs proven by `nexus-network bench`, from cheap to expensive
+    pub const BENCH_INPUTS: [(u32, u32, u32); 3] = [(1_000, 1, 1), (5_000, 1, 1), (10_000, 1, 1)];
+
+    /// Highest parallelism tested by default, matching the worker thread cap
+    pub const BENCH_MAX_PARALLEL: usize = 8;
+
+    /// Minimum throughput gain required before recommending an additional thread
+    pub const BENCH_MIN_SPEEDUP: f64 = 1.1;
+
+    /// Fraction of total memory the provers may use before a level is considered unsafe
+    pub const BENCH_MEMORY_HEADROOM: f64 = 0.9;
+
+    /// Interval at which prover subprocess memory is sampled during a benchmark
+    pub const BENCH_RSS_SAMPLE_INTERVAL_MS: u64 = 250;
+
+    /// Calibrated proofs per minute required for `--max-difficulty auto` to request medium tasks
+    pub const AUTO_DIFFICULTY_MEDIUM_MIN_PPM: f64 = 2.0;
+
+    /// Calibrated proofs per minute required for `--max-difficulty auto` to request large tasks
+    pub const AUTO_DIFFICULTY_LARGE_MIN_PPM: f64 = 6.0;
+
+    // =============================================================================
+    // DIAGNOSTICS CONFIGURATION
+    // =============================================================================
+
+    /// Orchestrator round-trip time above which `doctor` reports a warning
+    pub const DOCTOR_LATENCY_WARN_MS: u128 = 1_500;
+
+    /// Clock skew against the orchestrator above which `doctor` reports a warning
+    pub const DOCTOR_CLOCK_SKEW_WARN_SECS: i64 = 30;
+
+    /// Clock skew against the orchestrator above which `doctor` reports a failure
+    pub const DOCTOR_CLOCK_SKEW_FAIL_SECS: i64 = 300;
+
+    /// Time without task progress after which a headless node reports itself unhealthy
+    pub const HEALTH_STALL_TIMEOUT_SECS: u64 = 30 * 60;
+
+    // =============================================================================
+    // CONFIG FILE
+    // =============================================================================
+
+    /// Layout version written to config files; older files are migrated when loaded
+    pub const CONFIG_VERSION: u32 = 2;
+
+    /// Longest wait for another process to release a config file lock
+    pub const FILE_LOCK_TIMEOUT_SECS: u64 = 5;
+
+    /// Interval between attempts to take a held file lock
+    pub const FILE_LOCK_POLL_MS: u64 = 50;
+
     // =============================================================================
     // NETWORK CONFIGURATION
     // =============================================================================
@@ -43,18 +139,34 @@ pub mod cli_consts {
         /// Maximum number of retry attempts for task fetching
         pub const MAX_RETRIES: u32 = 2;
 
-        /// Minimum interval between task fetch requests (milliseconds)
-        /// Set to 2 minutes to align with server task creation frequency
-        pub const RATE_LIMIT_INTERVAL_MS: u64 = 120_000;
+        /// Maximum delay between failed task fetches (milliseconds)
+        pub const MAX_BACKOFF_MS: u64 = 600_000;
+
+        /// Default lower bound of the adaptive fetch cadence (seconds)
+        pub const MIN_FETCH_INTERVAL_SECS: u64 = 15;
+
+        /// Default upper bound of the adaptive fetch cadence (seconds)
+        pub const MAX_FETCH_INTERVAL_SECS: u64 = 600;
+
+        /// How long a rate-limited fetch keeps slowing the cadence down (seconds)
+        pub const RATE_LIMIT_MEMORY_SECS: u64 = 600;
+
+        /// Weight of the latest proof in the running proving time estimate
+        pub const PROVING_TIME_SMOOTHING: f64 = 0.3;
 
         /// Helper function to get initial backoff duration
         pub const fn initial_backoff() -> Duration {
             Duration::from_millis(INITIAL_BACKOFF_MS)
         }
 
-        /// Helper function to get rate limit interval
-        pub const fn rate_limit_interval() -> Duration {
-            Duration::from_millis(RATE_LIMIT_INTERVAL_MS)
+        /// Helper function to get maximum backoff duration
+        pub const fn max_backoff() -> Duration {
+            Duration::from_millis(MAX_BACKOFF_MS)
+        }
+
+        /// Helper function to get how long a rate-limited fetch is remembered
+        pub const fn rate_limit_memory() -> Duration {
+            Duration::from_secs(RATE_LIMIT_MEMORY_SECS)
         }
     }
 
@@ -70,6 +182,9 @@ pub mod cli_consts {
         /// More retries since submissions are critical
         pub const MAX_RETRIES: u32 = 5;
 
+        /// Maximum delay between failed submission attempts (milliseconds)
+        pub const MAX_BACKOFF_MS: u64 = 60_000;
+
         /// Minimum interval between submission requests (milliseconds)
         /// Less restrictive than task fetching
         pub const RATE_LIMIT_INTERVAL_MS: u64 = 100;
@@ -79,12 +194,41 @@ pub mod cli_consts {
             Duration::from_millis(INITIAL_BACKOFF_MS)
         }
 
+        /// Helper function to get maximum backoff duration
+        pub const fn max_backoff() -> Duration {
+            Duration::from_millis(MAX_BACKOFF_MS)
+        }
+
         /// Helper function to get rate limit interval
         pub const fn rate_limit_interval() -> Duration {
             Duration::from_millis(RATE_LIMIT_INTERVAL_MS)
         }
     }
 
+    /// Registration and node lookup retry configuration
+    pub mod registration {
+        use std::time::Duration;
+
+        /// Initial delay before retrying a failed registration or node lookup (milliseconds)
+        pub const INITIAL_BACKOFF_MS: u64 = 1000;
+
+        /// Maximum delay between attempts (milliseconds)
+        pub const MAX_BACKOFF_MS: u64 = 10_000;
+
+        /// Maximum number of attempts, kept low since a user is waiting interactively
+        pub const MAX_RETRIES: u32 = 3;
+
+        /// Helper function to get initial backoff duration
+        pub const fn initial_backoff() -> Duration {
+            Duration::from_millis(INITIAL_BACKOFF_MS)
+        }
+
+        /// Helper function to get maximum backoff duration
+        pub const fn max_backoff() -> Duration {
+            Duration::from_millis(MAX_BACKOFF_MS)
+        }
+    }
+
     /// Advanced rate limiting configuration
     pub mod rate_limiting {
         use std::time::Duration;
@@ -111,12 +255,111 @@ pub mod cli_consts {
             Duration::from_millis(SUBMISSION_WINDOW_MS)
         }
 
-        /// Extra delay to add on top of server-provided retry delays
+        /// Maximum random extra delay added on top of server-provided retry delays
         pub const EXTRA_RETRY_DELAY_SECS: u64 = 10;
 
         /// Helper function to get the extra retry delay
         pub const fn extra_retry_delay() -> Duration {
             Duration::from_secs(EXTRA_RETRY_DELAY_SECS)
         }
+
+        /// Upper bound on server-provided retry delays (seconds)
+        pub const MAX_SERVER_RETRY_DELAY_SECS: u64 = 600;
+
+        /// Helper function to get the maximum server-provided retry delay
+        pub const fn max_server_retry_delay() -> Duration {
+            Duration::from_secs(MAX_SERVER_RETRY_DELAY_SECS)
+        }
+    }
+
+    /// Circuit breaker configuration for orchestrator requests
+    pub mod circuit_breaker {
+        use std::time::Duration;
+
+        /// Consecutive outage-like failures (network errors, 429, 5xx) before the circuit opens
+        pub const FAILURE_THRESHOLD: u32 = 5;
+
+        /// Time an open circuit waits before sending a half-open probe (seconds)
+        pub const COOLDOWN_SECS: u64 = 300;
+
+        /// Helper function to get the cooldown duration
+        pub const fn cooldown() -> Duration {
+            Duration::from_secs(COOLDOWN_SECS)
+        }
+    }
+
+    /// Event sink configuration
+    pub mod event_sinks {
+        use std::time::Duration;
+
+        /// Size at which a log file sink rotates its file (bytes)
+        pub const FILE_MAX_BYTES: u64 = 10 * 1024 * 1024; // 10 MiB
+
+        /// Number of rotated log files kept by a file sink
+        pub const FILE_KEEP: usize = 5;
+
+        /// Timeout for a single webhook request (seconds)
+        pub const WEBHOOK_TIMEOUT_SECS: u64 = 10;
+
+        /// Number of webhook posts that can wait for delivery before new ones are dropped
+        pub const WEBHOOK_QUEUE_SIZE: usize = 100;
+
+        /// Time allowed for sinks to deliver remaining events on shutdown (seconds)
+        pub const SHUTDOWN_TIMEOUT_SECS: u64 = 5;
+
+        /// Helper function to get the webhook request timeout
+        pub const fn webhook_timeout() -> Duration {
+            Duration::from_secs(WEBHOOK_TIMEOUT_SECS)
+        }
+
+        /// Helper function to get the shutdown timeout
+        pub const fn shutdown_timeout() -> Duration {
+            Duration::from_secs(SHUTDOWN_TIMEOUT_SECS)
+        }
     }
+
+    /// Exit codes of `nexus-network setup`, one per failure mode
+    pub mod setup_exit_codes {
+        /// The wallet address is malformed or fails its checksum
+        pub const INVALID_WALLET: i32 = 2;
+
+        /// The existing config file could not be read
+        pub const CONFIG_UNREADABLE: i32 = 3;
+
+        /// The config file could not be written
+        pub const CONFIG_UNWRITABLE: i32 = 4;
+
+        /// The existing config file belongs to a different wallet
+        pub const CONFIG_WALLET_MISMATCH: i32 = 5;
+
+        /// The orchestrator could not be reached or rejected a request
+        pub const ORCHESTRATOR: i32 = 6;
+
+        /// The requested node does not exist
+        pub const NODE_NOT_FOUND: i32 = 7;
+
+        /// The requested node belongs to a different wallet
+        pub const NODE_WALLET_MISMATCH: i32 = 8;
+    }
+
+    /// Exit codes of `nexus-network start`, so batch runs can tell why the node stopped
+    pub mod run_exit_codes {
+        /// All `--max-tasks` tasks were submitted, or a run without a limit was stopped
+        pub const COMPLETED: i32 = 0;
+
+        /// A `--max-tasks` run was stopped before finishing
+        pub const INTERRUPTED: i32 = 10;
+
+        /// This version is blocked by the version requirements
+        pub const VERSION_BLOCKED: i32 = 11;
+
+        /// The node could not be authenticated or its configuration is invalid
+        pub const CONFIG: i32 = 12;
+
+        /// Too many proofs in a row failed during a `--max-tasks` run
+        pub const PROVING_FAILURES: i32 = 13;
+    }
+
+    /// Consecutive proving failures after which a `--max-tasks` run gives up
+    pub const MAX_CONSECUTIVE_PROVING_FAILURES: u32 = 5;
 }
@@ -0,0 +1,357 @@
+//! Health tracking and the `/healthz` and `/readyz` endpoint
+//!
+//! [`HealthSink`] follows the worker events to learn the prover state, the orchestrator
+//! circuit and when a task last made progress: an input was proven or a proof submitted.
+//! Fetching a task, or a state change, is not progress, so a node whose every proof fails
+//! still stalls. The node is unhealthy when no task has
+//! progressed for the stall timeout, unless it is paused on purpose, or while the
+//! orchestrator circuit is open. It is ready once it has started and the circuit is not
+//! open. The HTTP server answers `GET /healthz` and `GET /readyz` with 200 or 503 and a
+//! JSON body, enough for Docker and Kubernetes health checks.
+
+use super::notify::Notifier;
+use crate::event_bus::{EventSink, SinkError};
+use crate::events::{Event, EventType, ProverState, Worker};
+use crate::network::CircuitState;
+use serde::Serialize;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// What the node has reported about itself, shared by the sink and the endpoint
+#[derive(Debug, Clone)]
+pub struct Health {
+    pub ready: bool,
+    pub state: ProverState,
+    pub circuit: CircuitState,
+    pub last_progress: Instant,
+    /// Message of the last state change
+    pub status: String,
+}
+
+impl Health {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            ready: false,
+            state: ProverState::Waiting,
+            circuit: CircuitState::Closed,
+            last_progress: now,
+            status: "Starting".to_string(),
+        }
+    }
+
+    /// Take an event into account, returning whether the prover state changed
+    pub fn observe(&mut self, event: &Event, now: Instant) -> bool {
+        if let Some(circuit) = event.circuit_state {
+            self.circuit = circuit;
+        }
+        // Entering the proving state is not progress either, as it starts every failing attempt
+        let progressed = event.proving_progress.is_some()
+            || (event.worker == Worker::ProofSubmitter && event.event_type == EventType::Success);
+        if progressed {
+            self.last_progress = now;
+        }
+        match event.prover_state {
+            Some(state) if event.event_type == EventType::StateChange => {
+                // A pause is no stall, so the stall timeout runs from when it starts or ends
+                if (state == ProverState::Paused) != (self.state == ProverState::Paused) {
+                    self.last_progress = now;
+                }
+                let changed = state != self.state || event.msg != self.status;
+                self.state = state;
+                self.status = event.msg.clone();
+                changed
+            }
+            _ => false,
+        }
+    }
+
+    pub fn report(&self, now: Instant, stall_timeout: Duration) -> HealthReport {
+        let since_progress = now.saturating_duration_since(self.last_progress);
+        let stalled = self.state != ProverState::Paused && since_progress > stall_timeout;
+        let problem = if self.circuit == CircuitState::Open {
+            Some("the orchestrator is unreachable".to_string())
+        } else if stalled {
+            Some(format!(
+                "no task has progressed for {} seconds",
+                since_progress.as_secs()
+            ))
+        } else {
+            None
+        };
+        HealthReport {
+            healthy: problem.is_none(),
+            stalled,
+            ready: self.ready && self.circuit != CircuitState::Open,
+            state: self.state.to_string(),
+            circuit: self.circuit.to_string(),
+            secs_since_progress: since_progress.as_secs(),
+            status: self.status.clone(),
+            problem,
+        }
+    }
+}
+
+/// Body of the health endpoint
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct HealthReport {
+    pub healthy: bool,
+    /// Unhealthy because nothing has progressed, rather than because of the orchestrator
+    pub stalled: bool,
+    pub ready: bool,
+    pub state: String,
+    pub circuit: String,
+    pub secs_since_progress: u64,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub problem: Option<String>,
+}
+
+/// Keeps [`Health`] up to date from the event bus and mirrors the prover state to systemd
+pub struct HealthSink {
+    health: Arc<Mutex<Health>>,
+    notifier: Option<Notifier>,
+}
+
+impl HealthSink {
+    pub fn new(health: Arc<Mutex<Health>>, notifier: Option<Notifier>) -> Self {
+        Self { health, notifier }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventSink for HealthSink {
+    fn name(&self) -> &str {
+        "health"
+    }
+
+    async fn handle(&mut self, event: &Event) -> Result<(), SinkError> {
+        let changed = self
+            .health
+            .lock()
+            .map_err(|_| SinkError::Closed)?
+            .observe(event, Instant::now());
+        if let (true, Some(notifier)) = (changed, &self.notifier) {
+            notifier.status(&format!(
+                "{}: {}",
+                event.prover_state.unwrap_or(ProverState::Waiting),
+                event.msg
+            ))?;
+        }
+        Ok(())
+    }
+}
+
+/// Serve `/healthz` and `/readyz` on `addr` until the returned task is aborted
+pub async fn serve(
+    addr: SocketAddr,
+    health: Arc<Mutex<Health>>,
+    stall_timeout: Duration,
+) -> std::io::Result<JoinHandle<()>> {
+    let listener = TcpListener::bind(addr).await?;
+    Ok(tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let health = health.clone();
+            tokio::spawn(async move {
+                if let Err(e) = respond(stream, &health, stall_timeout).await {
+                    log::debug!("Health check request failed: {}", e);
+                }
+            });
+        }
+    }))
+}
+
+async fn respond(
+    mut stream: TcpStream,
+    health: &Mutex<Health>,
+    stall_timeout: Duration,
+) -> std::io::Result<()> {
+    // Only the request line matters
+    let mut request = [0u8; 1024];
+    let read = stream.read(&mut request).await?;
+    let request = String::from_utf8_lossy(&request[..read]);
+    let path = request.split_whitespace().nth(1).unwrap_or_default();
+
+    let report = health
+        .lock()
+        .map(|health| health.report(Instant::now(), stall_timeout))
+        .map_err(|_| std::io::Error::other("health state poisoned"))?;
+    let (status, body) = match path {
+        "/healthz" | "/readyz" => {
+            let ok = if path == "/healthz" {
+                report.healthy
+            } else {
+                report.ready
+            };
+            let body = serde_json::to_string(&report).map_err(std::io::Error::other)?;
+            (
+                if ok {
+                    "200 OK"
+                } else {
+                    "503 Service Unavailable"
+                },
+                body,
+            )
+        }
+        _ => ("404 Not Found", r#"{"error":"not found"}"#.to_string()),
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logging::LogLevel;
+
+    fn state_change(state: ProverState) -> Event {
+        Event::state_change(state, format!("{} now", state))
+    }
+
+    #[test]
+    fn test_health_reports_stalls_and_open_circuit() {
+        let start = Instant::now();
+        let stall = Duration::from_secs(60);
+        let mut health = Health::new(start);
+        health.ready = true;
+        assert!(health.report(start, stall).healthy);
+
+        let later = start + Duration::from_secs(120);
+        let report = health.report(later, stall);
+        assert!(!report.healthy && report.stalled);
+        assert_eq!(report.secs_since_progress, 120);
+
+        // A submitted proof is progress, and a deliberate pause keeps the node healthy
+        let submitted = Event::proof_submitter_with_level(
+            "Proof submitted".to_string(),
+            EventType::Success,
+            LogLevel::Info,
+        );
+        assert!(!health.observe(&submitted, later));
+        assert_eq!(health.report(later, stall).secs_since_progress, 0);
+        assert!(health.observe(&state_change(ProverState::Paused), later));
+        assert!(
+            health
+                .report(later + Duration::from_secs(600), stall)
+                .healthy
+        );
+
+        let mut open = Event::task_fetcher_with_level(
+            "Circuit opened".to_string(),
+            EventType::Error,
+            LogLevel::Warn,
+        );
+        open.circuit_state = Some(CircuitState::Open);
+        assert!(!health.observe(&open, later));
+        let report = health.report(later, stall);
+        assert!(!report.healthy && !report.stalled && !report.ready);
+    }
+
+    #[test]
+    fn test_resuming_from_a_pause_is_not_a_stall() {
+        let start = Instant::now();
+        let stall = Duration::from_secs(60);
+        let mut health = Health::new(start);
+        health.observe(&state_change(ProverState::Paused), start);
+
+        // Overnight outside the schedule window, then back to work
+        let resumed = start + Duration::from_secs(8 * 3600);
+        assert!(health.report(resumed, stall).healthy);
+        health.observe(&state_change(ProverState::Waiting), resumed);
+        let report = health.report(resumed + Duration::from_secs(30), stall);
+        assert!(report.healthy && !report.stalled);
+        assert!(
+            health
+                .report(resumed + Duration::from_secs(120), stall)
+                .stalled
+        );
+    }
+
+    /// One fetch, prove and fail cycle of a node whose proofs always fail
+    fn failing_cycle() -> Vec<Event> {
+        vec![
+            Event::task_fetcher_with_level(
+                "Got task".to_string(),
+                EventType::Success,
+                LogLevel::Info,
+            ),
+            state_change(ProverState::Proving),
+            Event::prover_with_level(
+                0,
+                "Proof generation failed".to_string(),
+                EventType::Error,
+                LogLevel::Error,
+            ),
+            state_change(ProverState::Waiting),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_failing_proofs_stall_the_node() {
+        let stall = Duration::from_millis(200);
+        let health = Arc::new(Mutex::new(Health::new(Instant::now())));
+        health.lock().unwrap().ready = true;
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        let server = serve(addr, health.clone(), stall).await.unwrap();
+
+        for _ in 0..6 {
+            for event in failing_cycle() {
+                health.lock().unwrap().observe(&event, Instant::now());
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET /healthz HTTP/1.1\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 503"), "{}", response);
+        assert!(response.contains(r#""stalled":true"#));
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_endpoint_answers_with_status_codes() {
+        let health = Arc::new(Mutex::new(Health::new(Instant::now())));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        let server = serve(addr, health.clone(), Duration::from_secs(60))
+            .await
+            .unwrap();
+
+        let get = |path: &'static str| async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream
+                .write_all(format!("GET {} HTTP/1.1\r\n\r\n", path).as_bytes())
+                .await
+                .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        };
+
+        assert!(get("/healthz").await.starts_with("HTTP/1.1 200"));
+        assert!(get("/readyz").await.starts_with("HTTP/1.1 503"));
+        health.lock().unwrap().ready = true;
+        let ready = get("/readyz").await;
+        assert!(ready.starts_with("HTTP/1.1 200"));
+        assert!(ready.contains(r#""ready":true"#));
+        assert!(get("/metrics").await.starts_with("HTTP/1.1 404"));
+        server.abort();
+    }
+}
@@ -0,0 +1,147 @@
+//! Daemon support for headless mode
+//!
+//! Lets service managers and container runtimes supervise a headless node: readiness,
+//! status and watchdog notifications for systemd (see [`notify`]), an optional PID file,
+//! and an optional HTTP endpoint with `/healthz` and `/readyz` (see [`health`]).
+
+pub mod health;
+pub mod notify;
+
+use crate::consts::cli_consts::HEALTH_STALL_TIMEOUT_SECS;
+use crate::event_bus::EventSink;
+use clap::Args;
+use health::{Health, HealthSink};
+use notify::Notifier;
+use std::fs;
+use std::io;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+/// Command-line options for running headless mode as a supervised daemon
+#[derive(Args, Debug, Clone)]
+pub struct DaemonArgs {
+    /// Write the process ID to this file while the node runs
+    #[arg(long = "pid-file", value_name = "PATH", requires = "headless")]
+    pub pid_file: Option<PathBuf>,
+
+    /// Serve /healthz and /readyz on this address, e.g. 127.0.0.1:8080
+    #[arg(long = "health-addr", value_name = "ADDR", requires = "headless")]
+    pub health_addr: Option<SocketAddr>,
+
+    /// Report unhealthy when no task has progressed for this many seconds
+    #[arg(
+        long = "health-stall-timeout",
+        value_name = "SECS",
+        default_value_t = HEALTH_STALL_TIMEOUT_SECS,
+        requires = "headless"
+    )]
+    pub health_stall_timeout: u64,
+}
+
+/// The daemon facilities of a running headless session. Dropping it removes the PID file.
+pub struct Daemon {
+    health: Arc<Mutex<Health>>,
+    notifier: Option<Notifier>,
+    pid_file: Option<PathBuf>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl Daemon {
+    /// Write the PID file and start the health endpoint and watchdog, if requested
+    pub async fn start(args: &DaemonArgs) -> io::Result<Self> {
+        let health = Arc::new(Mutex::new(Health::new(Instant::now())));
+        let stall_timeout = Duration::from_secs(args.health_stall_timeout);
+        let mut daemon = Self {
+            health: health.clone(),
+            notifier: Notifier::from_env(),
+            pid_file: None,
+            tasks: Vec::new(),
+        };
+
+        if let Some(path) = &args.pid_file {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, format!("{}\n", std::process::id()))?;
+            daemon.pid_file = Some(path.clone());
+        }
+        if let Some(addr) = args.health_addr {
+            daemon
+                .tasks
+                .push(health::serve(addr, health.clone(), stall_timeout).await?);
+        }
+        if let (Some(notifier), Some(interval)) = (&daemon.notifier, notify::watchdog_interval()) {
+            daemon.tasks.push(tokio::spawn(watchdog(
+                notifier.clone(),
+                health,
+                interval,
+                stall_timeout,
+            )));
+        }
+        Ok(daemon)
+    }
+
+    /// Sink keeping the health state, and the systemd status, up to date
+    pub fn sink(&self) -> Box<dyn EventSink> {
+        Box::new(HealthSink::new(self.health.clone(), self.notifier.clone()))
+    }
+
+    /// Report that the node has started
+    pub fn ready(&self) {
+        if let Ok(mut health) = self.health.lock() {
+            health.ready = true;
+        }
+        if let Some(notifier) = &self.notifier {
+            if let Err(e) = notifier.ready("Started") {
+                log::warn!("Failed to notify systemd: {}", e);
+            }
+        }
+    }
+
+    /// Report that the node is shutting down
+    pub fn stopping(&self) {
+        if let Ok(mut health) = self.health.lock() {
+            health.ready = false;
+        }
+        if let Some(notifier) = &self.notifier {
+            let _ = notifier.stopping();
+        }
+    }
+}
+
+impl Drop for Daemon {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+        if let Some(path) = &self.pid_file {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// Ping the systemd watchdog at half its interval while tasks keep progressing, so a wedged
+/// node is restarted. An unreachable orchestrator is not a reason to restart.
+async fn watchdog(
+    notifier: Notifier,
+    health: Arc<Mutex<Health>>,
+    interval: Duration,
+    stall_timeout: Duration,
+) {
+    let mut ticker = tokio::time::interval(interval / 2);
+    loop {
+        ticker.tick().await;
+        let stalled = health
+            .lock()
+            .map(|health| health.report(Instant::now(), stall_timeout).stalled)
+            .unwrap_or(true);
+        if !stalled {
+            if let Err(e) = notifier.watchdog() {
+                log::warn!("Failed to ping the systemd watchdog: {}", e);
+            }
+        }
+    }
+}
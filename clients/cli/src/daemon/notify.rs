@@ -0,0 +1,119 @@
+//! systemd notifications
+//!
+//! A minimal `sd_notify` client: when systemd starts the node with `Type=notify`, it sets
+//! `NOTIFY_SOCKET` to a datagram socket that accepts newline-separated `KEY=VALUE`
+//! assignments such as `READY=1`, `STATUS=...` and `WATCHDOG=1`. Outside systemd, or on
+//! platforms without Unix sockets, there is no notifier and nothing is sent.
+
+use std::time::Duration;
+
+/// Sends state notifications to the service manager
+#[derive(Debug, Clone)]
+pub struct Notifier {
+    #[cfg_attr(not(unix), allow(dead_code))]
+    socket: String,
+}
+
+impl Notifier {
+    /// The notifier systemd asked for, if any
+    pub fn from_env() -> Option<Self> {
+        if !cfg!(unix) {
+            return None;
+        }
+        std::env::var("NOTIFY_SOCKET")
+            .ok()
+            .filter(|socket| !socket.is_empty())
+            .map(|socket| Self { socket })
+    }
+
+    /// Tell the service manager startup has finished
+    pub fn ready(&self, status: &str) -> std::io::Result<()> {
+        self.send(&format!("READY=1\nSTATUS={}", one_line(status)))
+    }
+
+    /// Update the status line shown by `systemctl status`
+    pub fn status(&self, status: &str) -> std::io::Result<()> {
+        self.send(&format!("STATUS={}", one_line(status)))
+    }
+
+    /// Reset the service manager's watchdog timer
+    pub fn watchdog(&self) -> std::io::Result<()> {
+        self.send("WATCHDOG=1")
+    }
+
+    /// Tell the service manager the node is shutting down
+    pub fn stopping(&self) -> std::io::Result<()> {
+        self.send("STOPPING=1\nSTATUS=Shutting down")
+    }
+
+    #[cfg(unix)]
+    fn send(&self, state: &str) -> std::io::Result<()> {
+        use std::os::unix::net::UnixDatagram;
+
+        let socket = UnixDatagram::unbound()?;
+        match self.socket.strip_prefix('@') {
+            // A leading '@' names a socket in the abstract namespace
+            #[cfg(target_os = "linux")]
+            Some(name) => {
+                use std::os::linux::net::SocketAddrExt;
+                let addr = std::os::unix::net::SocketAddr::from_abstract_name(name.as_bytes())?;
+                socket.send_to_addr(state.as_bytes(), &addr)?;
+            }
+            _ => {
+                socket.send_to(state.as_bytes(), &self.socket)?;
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    fn send(&self, _state: &str) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Keep text on one line, since each line is a separate assignment
+fn one_line(text: &str) -> String {
+    text.replace(['\r', '\n'], " ")
+}
+
+/// How often the service manager expects a watchdog ping, from `WATCHDOG_USEC`, if this
+/// process is the one being watched
+pub fn watchdog_interval() -> Option<Duration> {
+    let watched = std::env::var("WATCHDOG_PID")
+        .ok()
+        .and_then(|pid| pid.parse::<u32>().ok())
+        .is_none_or(|pid| pid == std::process::id());
+    let usec = std::env::var("WATCHDOG_USEC")
+        .ok()?
+        .parse::<u64>()
+        .ok()
+        .filter(|usec| *usec > 0)?;
+    watched.then(|| Duration::from_micros(usec))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixDatagram;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_notifications_reach_the_socket() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("notify.sock");
+        let receiver = UnixDatagram::bind(&path).unwrap();
+        let notifier = Notifier {
+            socket: path.to_string_lossy().into_owned(),
+        };
+
+        notifier.ready("Proving: task 1\r").unwrap();
+        let mut buf = [0u8; 256];
+        let len = receiver.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..len], b"READY=1\nSTATUS=Proving: task 1 ");
+
+        notifier.watchdog().unwrap();
+        let len = receiver.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..len], b"WATCHDOG=1");
+    }
+}
@@ -0,0 +1,53 @@
+//! Console sink
+//!
+//! Prints events to stdout, either in the human-readable headless format or as JSON lines
+//! for log collectors.
+
+use super::{EventRecord, EventSink, SinkError};
+use crate::events::Event;
+use crate::logging::LogLevel;
+
+#[derive(Debug)]
+pub struct ConsoleSink {
+    json: bool,
+    min_level: LogLevel,
+}
+
+impl ConsoleSink {
+    /// Human-readable output, printing every event as headless mode always has
+    pub fn plain() -> Self {
+        Self {
+            json: false,
+            min_level: LogLevel::Trace,
+        }
+    }
+
+    /// One JSON object per line
+    pub fn json(min_level: LogLevel) -> Self {
+        Self {
+            json: true,
+            min_level,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventSink for ConsoleSink {
+    fn name(&self) -> &str {
+        if self.json { "json_stdout" } else { "stdout" }
+    }
+
+    async fn handle(&mut self, event: &Event) -> Result<(), SinkError> {
+        if event.log_level < self.min_level {
+            return Ok(());
+        }
+        if self.json {
+            let line = serde_json::to_string(&EventRecord::from(event))
+                .map_err(|e| SinkError::Io(e.into()))?;
+            println!("{}", line);
+        } else {
+            println!("{}", event);
+        }
+        Ok(())
+    }
+}
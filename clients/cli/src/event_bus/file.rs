@@ -0,0 +1,101 @@
+//! File sink
+//!
+//! Appends events to a log file, rotating it by size and optionally by hour or day. See
+//! [`crate::log_file`] for the rotation scheme.
+
+use super::{EventSink, SinkError};
+use crate::events::Event;
+use crate::log_file::{RotatingFile, Rotation};
+use crate::logging::LogLevel;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub struct FileSink {
+    file: RotatingFile,
+    min_level: LogLevel,
+}
+
+impl FileSink {
+    pub fn open(
+        path: PathBuf,
+        rotation: Rotation,
+        keep: usize,
+        min_level: LogLevel,
+    ) -> Result<Self, SinkError> {
+        Ok(Self {
+            file: RotatingFile::open(path, rotation, keep)?,
+            min_level,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl EventSink for FileSink {
+    fn name(&self) -> &str {
+        "file"
+    }
+
+    async fn handle(&mut self, event: &Event) -> Result<(), SinkError> {
+        if event.log_level < self.min_level {
+            return Ok(());
+        }
+        let line = format!(
+            "{} {:<5} {:<10} {}",
+            event.timestamp,
+            event.log_level.as_str().to_uppercase(),
+            event.worker.to_string(),
+            event.msg
+        );
+        self.file.write_line(&line)?;
+        if let Some(failure) = &event.task_failure {
+            for stderr_line in &failure.stderr_tail {
+                self.file.write_line(&format!("    | {}", stderr_line))?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<(), SinkError> {
+        self.file.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventType;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn event(msg: &str, log_level: LogLevel) -> Event {
+        Event::proof_submitter_with_level(msg.to_string(), EventType::Success, log_level)
+    }
+
+    #[tokio::test]
+    async fn test_writes_events_at_or_above_min_level() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("logs").join("nexus.log");
+        let rotation = Rotation {
+            max_bytes: Some(100),
+            interval: None,
+        };
+        let mut sink = FileSink::open(path.clone(), rotation, 2, LogLevel::Info).unwrap();
+
+        for i in 0..2 {
+            sink.handle(&event(&format!("{}{}", i, "x".repeat(40)), LogLevel::Info))
+                .await
+                .unwrap();
+        }
+        sink.handle(&event("debug detail", LogLevel::Debug))
+            .await
+            .unwrap();
+        sink.close().await.unwrap();
+
+        let current = fs::read_to_string(&path).unwrap();
+        let rotated = fs::read_to_string(dir.path().join("logs").join("nexus.log.1")).unwrap();
+        assert!(current.contains("INFO  submitter  1xxx"));
+        assert!(rotated.contains(" 0xxx"));
+        assert!(!current.contains("debug detail"));
+    }
+}
@@ -0,0 +1,54 @@
+//! Log sink
+//!
+//! Forwards events to the [`log`] backend installed by `--log-file`, so worker events and
+//! other diagnostics share one file and one level filter.
+
+use super::{EventSink, SinkError};
+use crate::events::{Event, EventType};
+
+#[derive(Debug, Default)]
+pub struct LogSink;
+
+impl LogSink {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait::async_trait]
+impl EventSink for LogSink {
+    fn name(&self) -> &str {
+        "log"
+    }
+
+    async fn handle(&mut self, event: &Event) -> Result<(), SinkError> {
+        // State changes only drive the dashboard
+        if event.event_type == EventType::StateChange {
+            return Ok(());
+        }
+        log::log!(
+            target: "nexus_network::events",
+            event.log_level.into(),
+            "[{}] {}",
+            event.worker,
+            event.msg
+        );
+        if let Some(failure) = &event.task_failure {
+            for line in &failure.stderr_tail {
+                log::log!(
+                    target: "nexus_network::events",
+                    event.log_level.into(),
+                    "[{}]   | {}",
+                    event.worker,
+                    line
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<(), SinkError> {
+        log::logger().flush();
+        Ok(())
+    }
+}
@@ -0,0 +1,465 @@
+//! Event Bus
+//!
+//! Fans worker events out to any number of sinks. Workers keep sending into a bounded
+//! channel; the bus drains it and broadcasts each event to every subscribed [`EventSink`].
+//! A sink that falls behind skips events rather than slowing the workers down, except for
+//! lossless sinks such as the dashboard, which get every event and hold the bus back instead.
+
+pub mod console;
+pub mod file;
+pub mod log_sink;
+pub mod tui;
+pub mod webhook;
+
+use crate::consts::cli_consts::{EVENT_BUS_CAPACITY, event_sinks};
+use crate::events::Event;
+use crate::log_file::{Rotation, RotationInterval};
+use crate::logging::LogLevel;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use thiserror::Error;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+
+pub use console::ConsoleSink;
+pub use file::FileSink;
+pub use log_sink::LogSink;
+pub use tui::TuiSink;
+pub use webhook::{WebhookFormat, WebhookSink};
+
+#[derive(Debug, Error)]
+pub enum SinkError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Request failed: {0}")]
+    Request(#[from] reqwest::Error),
+
+    #[error("Webhook returned status {0}")]
+    Status(u16),
+
+    #[error("Sink is closed")]
+    Closed,
+
+    #[error("Too many events are waiting to be delivered; dropped this one")]
+    Backlog,
+}
+
+/// Serializable view of an event, used by the JSON outputs
+#[derive(Serialize, Debug)]
+pub struct EventRecord<'a> {
+    pub timestamp: &'a str,
+    pub level: &'static str,
+    pub worker: String,
+    pub event_type: String,
+    pub message: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stderr_tail: Option<&'a [String]>,
+}
+
+impl<'a> From<&'a Event> for EventRecord<'a> {
+    fn from(event: &'a Event) -> Self {
+        Self {
+            timestamp: &event.timestamp,
+            level: event.log_level.as_str(),
+            worker: event.worker.to_string(),
+            event_type: event.event_type.to_string(),
+            message: &event.msg,
+            stderr_tail: event
+                .task_failure
+                .as_ref()
+                .map(|failure| failure.stderr_tail.as_slice())
+                .filter(|tail| !tail.is_empty()),
+        }
+    }
+}
+
+/// A consumer of worker events
+#[async_trait::async_trait]
+pub trait EventSink: Send {
+    /// Short name used when reporting sink failures
+    fn name(&self) -> &str;
+
+    /// Handle a single event. A failure is reported but does not stop the sink.
+    async fn handle(&mut self, event: &Event) -> Result<(), SinkError>;
+
+    /// Flush any buffered output once no more events will arrive
+    async fn close(&mut self) -> Result<(), SinkError> {
+        Ok(())
+    }
+}
+
+/// An event sink selected in the config file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SinkConfig {
+    /// Append events to a log file, rotating it by size and optionally by time
+    File {
+        path: PathBuf,
+        #[serde(default = "default_max_bytes")]
+        max_bytes: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rotate: Option<RotationInterval>,
+        #[serde(default = "default_keep")]
+        keep: usize,
+        #[serde(default = "default_min_level")]
+        min_level: LogLevel,
+    },
+    /// POST events to a webhook, e.g. a Slack or Discord channel
+    Webhook {
+        url: String,
+        #[serde(default)]
+        format: WebhookFormat,
+        #[serde(default = "default_webhook_min_level")]
+        min_level: LogLevel,
+        /// Also post every time this many more proofs have been submitted
+        #[serde(default)]
+        milestone_every: Option<u32>,
+    },
+    /// Print events to stdout as JSON lines (headless mode only)
+    JsonStdout {
+        #[serde(default = "default_min_level")]
+        min_level: LogLevel,
+    },
+}
+
+fn default_max_bytes() -> u64 {
+    event_sinks::FILE_MAX_BYTES
+}
+
+fn default_keep() -> usize {
+    event_sinks::FILE_KEEP
+}
+
+fn default_min_level() -> LogLevel {
+    LogLevel::Info
+}
+
+fn default_webhook_min_level() -> LogLevel {
+    LogLevel::Warn
+}
+
+impl SinkConfig {
+    /// Whether the sink writes to the terminal, which the TUI owns while it runs
+    pub fn uses_stdout(&self) -> bool {
+        matches!(self, SinkConfig::JsonStdout { .. })
+    }
+
+    pub fn build(&self) -> Result<Box<dyn EventSink>, SinkError> {
+        let sink: Box<dyn EventSink> = match self {
+            SinkConfig::File {
+                path,
+                max_bytes,
+                rotate,
+                keep,
+                min_level,
+            } => {
+                let rotation = Rotation {
+                    max_bytes: Some(*max_bytes).filter(|bytes| *bytes > 0),
+                    interval: *rotate,
+                };
+                Box::new(FileSink::open(path.clone(), rotation, *keep, *min_level)?)
+            }
+            SinkConfig::Webhook {
+                url,
+                format,
+                min_level,
+                milestone_every,
+            } => Box::new(WebhookSink::new(
+                url.clone(),
+                *format,
+                *min_level,
+                *milestone_every,
+            )?),
+            SinkConfig::JsonStdout { min_level } => Box::new(ConsoleSink::json(*min_level)),
+        };
+        Ok(sink)
+    }
+}
+
+/// Broadcasts worker events to the registered sinks
+#[derive(Debug)]
+pub struct EventBus {
+    /// Held until [`EventBus::start`] hands it to the forwarding task
+    sender: Option<broadcast::Sender<Event>>,
+    /// Events from the workers, until the bus is started
+    source: Option<mpsc::Receiver<Event>>,
+    /// Queues of the lossless sinks, until the bus is started
+    lossless: Vec<mpsc::Sender<Event>>,
+    forwarder: Option<JoinHandle<()>>,
+    sinks: Vec<JoinHandle<()>>,
+}
+
+impl EventBus {
+    pub fn new(source: mpsc::Receiver<Event>) -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUS_CAPACITY);
+        Self {
+            sender: Some(sender),
+            source: Some(source),
+            lossless: Vec::new(),
+            forwarder: None,
+            sinks: Vec::new(),
+        }
+    }
+
+    /// Subscribe a sink. Sinks must be added before the bus is started.
+    pub fn add_sink(&mut self, mut sink: Box<dyn EventSink>) {
+        let Some(sender) = &self.sender else {
+            return;
+        };
+        let mut receiver = sender.subscribe();
+        self.sinks.push(tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => handle_event(sink.as_mut(), &event).await,
+                    Err(RecvError::Lagged(missed)) => {
+                        log::warn!("Event sink {} skipped {} events", sink.name(), missed);
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+            close_sink(sink.as_mut()).await;
+        }));
+    }
+
+    /// Subscribe a sink that must see every event, such as the dashboard, whose state is
+    /// built from them. Rather than skipping events when it falls behind, the sink holds
+    /// the bus back. Sinks must be added before the bus is started.
+    pub fn add_lossless_sink(&mut self, mut sink: Box<dyn EventSink>) {
+        if self.sender.is_none() {
+            return;
+        }
+        let (sender, mut receiver) = mpsc::channel(EVENT_BUS_CAPACITY);
+        self.lossless.push(sender);
+        self.sinks.push(tokio::spawn(async move {
+            while let Some(event) = receiver.recv().await {
+                handle_event(sink.as_mut(), &event).await;
+            }
+            close_sink(sink.as_mut()).await;
+        }));
+    }
+
+    /// Build and subscribe the sinks selected in the config. Sinks that fail to build are
+    /// skipped and returned with their error so the caller can report them.
+    pub fn add_configured_sinks(&mut self, configs: &[SinkConfig]) -> Vec<(SinkConfig, SinkError)> {
+        let mut failures = Vec::new();
+        for config in configs {
+            match config.build() {
+                Ok(sink) => self.add_sink(sink),
+                Err(e) => failures.push((config.clone(), e)),
+            }
+        }
+        failures
+    }
+
+    /// Start forwarding worker events to the sinks
+    pub fn start(&mut self) {
+        let (Some(sender), Some(mut source)) = (self.sender.take(), self.source.take()) else {
+            return;
+        };
+        let lossless = std::mem::take(&mut self.lossless);
+        self.forwarder = Some(tokio::spawn(async move {
+            while let Some(event) = source.recv().await {
+                for queue in &lossless {
+                    // A lossless sink only goes away when it has stopped for good
+                    let _ = queue.send(event.clone()).await;
+                }
+                // No subscribers is not an error; the event is simply dropped
+                let _ = sender.send(event);
+            }
+        }));
+    }
+
+    /// Deliver the remaining events and wait for the sinks to flush, giving up after a timeout
+    pub async fn shutdown(mut self) {
+        // Dropping the senders of an unstarted bus closes the sinks directly
+        self.sender.take();
+        self.lossless.clear();
+        let grace = event_sinks::shutdown_timeout();
+        if let Some(forwarder) = self.forwarder.take() {
+            let abort = forwarder.abort_handle();
+            if timeout(grace, forwarder).await.is_err() {
+                abort.abort();
+            }
+        }
+        for sink in self.sinks.drain(..) {
+            let abort = sink.abort_handle();
+            if timeout(grace, sink).await.is_err() {
+                abort.abort();
+            }
+        }
+    }
+}
+
+async fn handle_event(sink: &mut dyn EventSink, event: &Event) {
+    if let Err(e) = sink.handle(event).await {
+        log::warn!("Event sink {} failed: {}", sink.name(), e);
+    }
+}
+
+async fn close_sink(sink: &mut dyn EventSink) {
+    if let Err(e) = sink.close().await {
+        log::warn!("Event sink {} failed to close: {}", sink.name(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{EventType, ProverState};
+    use std::sync::{Arc, Mutex};
+
+    struct CollectingSink {
+        events: Arc<Mutex<Vec<String>>>,
+        closed: Arc<Mutex<bool>>,
+    }
+
+    #[async_trait::async_trait]
+    impl EventSink for CollectingSink {
+        fn name(&self) -> &str {
+            "collect"
+        }
+
+        async fn handle(&mut self, event: &Event) -> Result<(), SinkError> {
+            self.events.lock().unwrap().push(event.msg.clone());
+            Ok(())
+        }
+
+        async fn close(&mut self) -> Result<(), SinkError> {
+            *self.closed.lock().unwrap() = true;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_every_sink_receives_every_event() {
+        let (sender, receiver) = mpsc::channel(10);
+        let mut bus = EventBus::new(receiver);
+
+        let collected: Vec<_> = (0..2)
+            .map(|_| {
+                (
+                    Arc::new(Mutex::new(Vec::new())),
+                    Arc::new(Mutex::new(false)),
+                )
+            })
+            .collect();
+        for (events, closed) in &collected {
+            bus.add_sink(Box::new(CollectingSink {
+                events: events.clone(),
+                closed: closed.clone(),
+            }));
+        }
+        bus.start();
+
+        for i in 0..3 {
+            sender
+                .send(Event::task_fetcher_with_level(
+                    format!("event {}", i),
+                    EventType::Refresh,
+                    LogLevel::Info,
+                ))
+                .await
+                .unwrap();
+        }
+        drop(sender);
+        bus.shutdown().await;
+
+        for (events, closed) in &collected {
+            assert_eq!(
+                *events.lock().unwrap(),
+                vec!["event 0", "event 1", "event 2"]
+            );
+            assert!(*closed.lock().unwrap());
+        }
+    }
+
+    /// Sink that holds off on its first event until released, then counts events
+    struct GatedSink {
+        gate: Option<tokio::sync::oneshot::Receiver<()>>,
+        handled: Arc<Mutex<usize>>,
+    }
+
+    #[async_trait::async_trait]
+    impl EventSink for GatedSink {
+        fn name(&self) -> &str {
+            "gated"
+        }
+
+        async fn handle(&mut self, _event: &Event) -> Result<(), SinkError> {
+            if let Some(gate) = self.gate.take() {
+                let _ = gate.await;
+            }
+            *self.handled.lock().unwrap() += 1;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lossless_sink_receives_every_event_when_behind() {
+        let (sender, receiver) = mpsc::channel(10);
+        let mut bus = EventBus::new(receiver);
+        let (release, gate) = tokio::sync::oneshot::channel();
+        let handled = Arc::new(Mutex::new(0));
+        bus.add_lossless_sink(Box::new(GatedSink {
+            gate: Some(gate),
+            handled: handled.clone(),
+        }));
+        bus.start();
+
+        // Far more events than the bus buffers arrive while the sink is stuck on the first
+        let total = 3 * EVENT_BUS_CAPACITY;
+        let producer = tokio::spawn(async move {
+            for i in 0..total {
+                sender
+                    .send(Event::state_change(
+                        ProverState::Proving,
+                        format!("event {}", i),
+                    ))
+                    .await
+                    .unwrap();
+            }
+        });
+        tokio::task::yield_now().await;
+        release.send(()).unwrap();
+        producer.await.unwrap();
+        bus.shutdown().await;
+
+        assert_eq!(*handled.lock().unwrap(), total);
+    }
+
+    #[test]
+    fn test_sink_config_parsing() {
+        let json = r#"[
+            {"type": "file", "path": "/tmp/nexus.log", "rotate": "daily"},
+            {"type": "webhook", "url": "https://example.com/hook", "format": "discord", "milestone_every": 100},
+            {"type": "json_stdout", "min_level": "debug"}
+        ]"#;
+        let configs: Vec<SinkConfig> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            configs,
+            vec![
+                SinkConfig::File {
+                    path: PathBuf::from("/tmp/nexus.log"),
+                    max_bytes: event_sinks::FILE_MAX_BYTES,
+                    rotate: Some(RotationInterval::Daily),
+                    keep: event_sinks::FILE_KEEP,
+                    min_level: LogLevel::Info,
+                },
+                SinkConfig::Webhook {
+                    url: "https://example.com/hook".to_string(),
+                    format: WebhookFormat::Discord,
+                    min_level: LogLevel::Warn,
+                    milestone_every: Some(100),
+                },
+                SinkConfig::JsonStdout {
+                    min_level: LogLevel::Debug,
+                },
+            ]
+        );
+        assert!(configs[2].uses_stdout());
+        assert!(!configs[0].uses_stdout());
+    }
+}
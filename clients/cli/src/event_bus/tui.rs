@@ -0,0 +1,32 @@
+//! TUI sink
+//!
+//! Hands events to the dashboard, which drains them on each frame.
+
+use super::{EventSink, SinkError};
+use crate::events::Event;
+use tokio::sync::mpsc;
+
+#[derive(Debug)]
+pub struct TuiSink {
+    sender: mpsc::Sender<Event>,
+}
+
+impl TuiSink {
+    pub fn new(sender: mpsc::Sender<Event>) -> Self {
+        Self { sender }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventSink for TuiSink {
+    fn name(&self) -> &str {
+        "tui"
+    }
+
+    async fn handle(&mut self, event: &Event) -> Result<(), SinkError> {
+        self.sender
+            .send(event.clone())
+            .await
+            .map_err(|_| SinkError::Closed)
+    }
+}
@@ -0,0 +1,254 @@
+//! Webhook sink
+//!
+//! POSTs notable events to a webhook: everything at or above a minimum level, plus a
+//! milestone message every N submitted proofs. Payloads can be shaped for Slack or Discord
+//! incoming webhooks, or sent as plain JSON event records. Requests are sent in the
+//! background, so a slow webhook does not make the sink fall behind the event bus.
+
+use super::{EventRecord, EventSink, SinkError};
+use crate::consts::cli_consts::event_sinks;
+use crate::events::{Event, EventType, Worker};
+use crate::logging::LogLevel;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::task::JoinHandle;
+
+/// Shape of the webhook request body
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookFormat {
+    /// `{"text": ...}`, for Slack incoming webhooks
+    Slack,
+    /// `{"content": ...}`, for Discord webhooks
+    Discord,
+    /// The event record as JSON
+    #[default]
+    Json,
+}
+
+#[derive(Debug)]
+pub struct WebhookSink {
+    client: Client,
+    url: String,
+    format: WebhookFormat,
+    min_level: LogLevel,
+    milestone_every: Option<u32>,
+    proofs_submitted: u32,
+    /// Payloads waiting to be posted, once the first one has started the delivery task
+    queue: Option<mpsc::Sender<serde_json::Value>>,
+    delivery: Option<JoinHandle<()>>,
+}
+
+impl WebhookSink {
+    pub fn new(
+        url: String,
+        format: WebhookFormat,
+        min_level: LogLevel,
+        milestone_every: Option<u32>,
+    ) -> Result<Self, SinkError> {
+        let client = Client::builder()
+            .timeout(event_sinks::webhook_timeout())
+            .build()?;
+        Ok(Self {
+            client,
+            url,
+            format,
+            min_level,
+            milestone_every: milestone_every.filter(|every| *every > 0),
+            proofs_submitted: 0,
+            queue: None,
+            delivery: None,
+        })
+    }
+
+    /// Count submitted proofs, returning a message when a milestone is reached
+    fn milestone(&mut self, event: &Event) -> Option<String> {
+        let every = self.milestone_every?;
+        if event.worker != Worker::ProofSubmitter || event.event_type != EventType::Success {
+            return None;
+        }
+        self.proofs_submitted += 1;
+        (self.proofs_submitted % every == 0)
+            .then(|| format!("Milestone: {} proofs submitted", self.proofs_submitted))
+    }
+
+    fn payload(&self, event: &Event, text: &str) -> serde_json::Value {
+        match self.format {
+            WebhookFormat::Slack => json!({ "text": text }),
+            WebhookFormat::Discord => json!({ "content": text }),
+            WebhookFormat::Json => json!(EventRecord {
+                message: text,
+                ..EventRecord::from(event)
+            }),
+        }
+    }
+
+    /// Queue a payload for the delivery task, starting it on first use
+    fn post(&mut self, payload: serde_json::Value) -> Result<(), SinkError> {
+        let queue = self.queue.get_or_insert_with(|| {
+            let (queue, mut payloads) = mpsc::channel(event_sinks::WEBHOOK_QUEUE_SIZE);
+            let (client, url) = (self.client.clone(), self.url.clone());
+            self.delivery = Some(tokio::spawn(async move {
+                while let Some(payload) = payloads.recv().await {
+                    if let Err(e) = send(&client, &url, &payload).await {
+                        log::warn!("Event sink webhook failed: {}", e);
+                    }
+                }
+            }));
+            queue
+        });
+        queue.try_send(payload).map_err(|e| match e {
+            TrySendError::Full(_) => SinkError::Backlog,
+            TrySendError::Closed(_) => SinkError::Closed,
+        })
+    }
+}
+
+async fn send(client: &Client, url: &str, payload: &serde_json::Value) -> Result<(), SinkError> {
+    let response = client.post(url).json(payload).send().await?;
+    if !response.status().is_success() {
+        return Err(SinkError::Status(response.status().as_u16()));
+    }
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl EventSink for WebhookSink {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn handle(&mut self, event: &Event) -> Result<(), SinkError> {
+        if event.event_type != EventType::StateChange && event.log_level >= self.min_level {
+            let text = format!(
+                "[nexus-network] {}: {}",
+                event.log_level.as_str().to_uppercase(),
+                event.msg
+            );
+            self.post(self.payload(event, &text))?;
+        }
+        if let Some(text) = self.milestone(event) {
+            self.post(self.payload(event, &format!("[nexus-network] {}", text)))?;
+        }
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<(), SinkError> {
+        // Closing the queue lets the delivery task post what is left and finish
+        self.queue.take();
+        if let Some(delivery) = self.delivery.take() {
+            let _ = delivery.await;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+
+    /// Minimal HTTP server standing in for a webhook endpoint; forwards each request body
+    async fn spawn_webhook_server() -> (String, mpsc::UnboundedReceiver<serde_json::Value>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                // Read until the full body announced by Content-Length has arrived
+                loop {
+                    let n = stream.read(&mut buf).await.unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request);
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let length = head
+                            .lines()
+                            .find_map(|line| {
+                                let (name, value) = line.split_once(':')?;
+                                name.eq_ignore_ascii_case("content-length")
+                                    .then(|| value.trim().parse::<usize>().ok())?
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= length {
+                            sender.send(serde_json::from_str(body).unwrap()).unwrap();
+                            break;
+                        }
+                    }
+                }
+                let _ = stream
+                    .write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                    .await;
+            }
+        });
+        (url, receiver)
+    }
+
+    #[tokio::test]
+    async fn test_posts_errors_and_milestones() {
+        let (url, mut requests) = spawn_webhook_server().await;
+        let mut sink =
+            WebhookSink::new(url, WebhookFormat::Slack, LogLevel::Warn, Some(2)).unwrap();
+
+        let submitted = Event::proof_submitter_with_level(
+            "Step 4 of 4: Proof submitted successfully".to_string(),
+            EventType::Success,
+            LogLevel::Info,
+        );
+        let failed = Event::task_fetcher_with_level(
+            "Failed to fetch task: Rate limited (HTTP 429)".to_string(),
+            EventType::Error,
+            LogLevel::Warn,
+        );
+
+        sink.handle(&submitted).await.unwrap();
+        sink.handle(&failed).await.unwrap();
+        sink.handle(&submitted).await.unwrap();
+
+        let first = requests.recv().await.unwrap();
+        assert_eq!(
+            first["text"],
+            "[nexus-network] WARN: Failed to fetch task: Rate limited (HTTP 429)"
+        );
+        let second = requests.recv().await.unwrap();
+        assert_eq!(
+            second["text"],
+            "[nexus-network] Milestone: 2 proofs submitted"
+        );
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_json_format_sends_event_record() {
+        let (url, mut requests) = spawn_webhook_server().await;
+        let mut sink = WebhookSink::new(url, WebhookFormat::Json, LogLevel::Error, None).unwrap();
+
+        let event = Event::prover_with_level(
+            1,
+            "Proof generation failed".to_string(),
+            EventType::Error,
+            LogLevel::Error,
+        );
+        sink.handle(&event).await.unwrap();
+
+        let body = requests.recv().await.unwrap();
+        assert_eq!(body["worker"], "prover-1");
+        assert_eq!(body["level"], "error");
+        assert_eq!(body["event_type"], "Error");
+        assert_eq!(
+            body["message"],
+            "[nexus-network] ERROR: Proof generation failed"
+        );
+    }
+}
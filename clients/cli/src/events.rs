@@ -3,8 +3,10 @@
 //! Types and implementations for worker events and logging
 
 use crate::logging::{LogLevel, should_log_with_env};
+use crate::network::CircuitState;
 use chrono::Local;
 use std::fmt::Display;
+use std::time::Duration;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum Worker {
@@ -16,6 +18,16 @@ pub enum Worker {
     ProofSubmitter,
 }
 
+impl Display for Worker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Worker::TaskFetcher => write!(f, "fetcher"),
+            Worker::Prover(thread_id) => write!(f, "prover-{}", thread_id),
+            Worker::ProofSubmitter => write!(f, "submitter"),
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, strum::Display)]
 pub enum EventType {
     Success,
@@ -32,6 +44,67 @@ pub enum ProverState {
     Proving,
     /// Waiting before fetching next task (idle state)
     Waiting,
+    /// Held back by the proving schedule or to let the machine cool down
+    Paused,
+}
+
+/// Per-input progress of the task currently being proven
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProvingProgress {
+    /// Task being proven
+    pub task_id: String,
+    /// Number of inputs proven so far
+    pub completed: usize,
+    /// Total number of inputs in the task
+    pub total: usize,
+    /// Time spent proving this task so far
+    pub elapsed: Duration,
+    /// Time taken by the most recently proven input
+    pub last_input_duration: Duration,
+    /// Rolling average time per input over the most recent inputs
+    pub average_input_duration: Duration,
+    /// Number of inputs being proven at once
+    pub parallelism: usize,
+}
+
+impl ProvingProgress {
+    /// Estimated time remaining, based on the rolling average duration per input and the
+    /// number of inputs proven at once
+    pub fn eta(&self) -> Duration {
+        let remaining = self.total.saturating_sub(self.completed);
+        let rounds = remaining.div_ceil(self.parallelism.max(1)) as u32;
+        self.average_input_duration * rounds
+    }
+
+    /// Completion ratio in the range [0.0, 1.0]
+    pub fn ratio(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            (self.completed as f64 / self.total as f64).min(1.0)
+        }
+    }
+}
+
+/// A task that failed to prove, kept with the event that reports it
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TaskFailure {
+    pub task_id: String,
+    pub error: String,
+    /// The last lines the prover subprocess wrote to stderr
+    pub stderr_tail: Vec<String>,
+}
+
+/// Format a duration compactly, e.g. "42s", "3m 05s" or "1h 02m"
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 {
+        format!("{}h {:02}m", secs / 3600, (secs % 3600) / 60)
+    } else if secs >= 60 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -43,6 +116,12 @@ pub struct Event {
     pub log_level: LogLevel,
     /// Optional state information for state change events
     pub prover_state: Option<ProverState>,
+    /// Optional per-input progress for proving progress events
+    pub proving_progress: Option<ProvingProgress>,
+    /// Optional new orchestrator circuit breaker state
+    pub circuit_state: Option<CircuitState>,
+    /// Optional details of a task that failed to prove
+    pub task_failure: Option<TaskFailure>,
 }
 
 impl PartialEq for Event {
@@ -53,6 +132,9 @@ impl PartialEq for Event {
             && self.event_type == other.event_type
             && self.log_level == other.log_level
             && self.prover_state == other.prover_state
+            && self.proving_progress == other.proving_progress
+            && self.circuit_state == other.circuit_state
+            && self.task_failure == other.task_failure
         // Note: We don't compare state_start_time since Instant doesn't implement Eq
     }
 }
@@ -68,6 +150,9 @@ impl Event {
             event_type,
             log_level,
             prover_state: None,
+            proving_progress: None,
+            circuit_state: None,
+            task_failure: None,
         }
     }
 
@@ -79,6 +164,57 @@ impl Event {
             event_type: EventType::StateChange,
             log_level: LogLevel::Info,
             prover_state: Some(state),
+            proving_progress: None,
+            circuit_state: None,
+            task_failure: None,
+        }
+    }
+
+    pub fn proving_progress(thread_id: usize, progress: ProvingProgress) -> Self {
+        let msg = format!(
+            "Step 2 of 4: Proved input {}/{} for task {} in {:.1}s (elapsed {}, ETA {})",
+            progress.completed,
+            progress.total,
+            progress.task_id,
+            progress.last_input_duration.as_secs_f64(),
+            format_duration(progress.elapsed),
+            format_duration(progress.eta()),
+        );
+        Self {
+            proving_progress: Some(progress),
+            ..Self::new(
+                Worker::Prover(thread_id),
+                msg,
+                EventType::Refresh,
+                LogLevel::Info,
+            )
+        }
+    }
+
+    pub fn circuit_state_change(worker: Worker, state: CircuitState, msg: String) -> Self {
+        let log_level = match state {
+            CircuitState::Open => LogLevel::Warn,
+            CircuitState::HalfOpen | CircuitState::Closed => LogLevel::Info,
+        };
+        Self {
+            circuit_state: Some(state),
+            ..Self::new(worker, msg, EventType::Refresh, log_level)
+        }
+    }
+
+    pub fn task_failed(thread_id: usize, failure: TaskFailure) -> Self {
+        let msg = format!(
+            "Proof generation failed for task {}: {}",
+            failure.task_id, failure.error
+        );
+        Self {
+            task_failure: Some(failure),
+            ..Self::new(
+                Worker::Prover(thread_id),
+                msg,
+                EventType::Error,
+                LogLevel::Error,
+            )
         }
     }
 
@@ -125,3 +261,40 @@ impl Display for Event {
         write!(f, "{} [{}] {}", self.event_type, self.timestamp, self.msg)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(completed: usize, total: usize, average_secs: u64) -> ProvingProgress {
+        ProvingProgress {
+            task_id: "task".to_string(),
+            completed,
+            total,
+            elapsed: Duration::from_secs(completed as u64 * average_secs),
+            last_input_duration: Duration::from_secs(average_secs),
+            average_input_duration: Duration::from_secs(average_secs),
+            parallelism: 1,
+        }
+    }
+
+    #[test]
+    fn test_eta_uses_average_for_remaining_inputs() {
+        assert_eq!(progress(5, 20, 12).eta(), Duration::from_secs(15 * 12));
+        assert_eq!(progress(20, 20, 12).eta(), Duration::ZERO);
+        let parallel = ProvingProgress {
+            parallelism: 4,
+            ..progress(5, 20, 12)
+        };
+        assert_eq!(parallel.eta(), Duration::from_secs(4 * 12));
+        assert_eq!(progress(0, 0, 12).ratio(), 0.0);
+        assert_eq!(progress(5, 20, 12).ratio(), 0.25);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(42)), "42s");
+        assert_eq!(format_duration(Duration::from_secs(185)), "3m 05s");
+        assert_eq!(format_duration(Duration::from_secs(3720)), "1h 02m");
+    }
+}
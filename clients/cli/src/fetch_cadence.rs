@@ -0,0 +1,213 @@
+//! Adaptive task fetch cadence
+//!
+//! After a successful fetch, [`FetchCadence`] decides how long to wait before the next one.
+//! A Retry-After hint from the orchestrator wins. Otherwise the next fetch is due about when
+//! the current task should be proven, from a running average of recent proving times, and
+//! every rate-limited fetch in the last few minutes doubles the wait. The result always stays
+//! within the configured bounds, and comes with a [`WaitReason`] for the dashboard.
+
+use crate::consts::cli_consts::task_fetching::{
+    MAX_FETCH_INTERVAL_SECS, MIN_FETCH_INTERVAL_SECS, PROVING_TIME_SMOOTHING, rate_limit_memory,
+};
+use clap::Args;
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Command-line bounds of the fetch cadence
+#[derive(Args, Debug, Clone)]
+pub struct FetchCadenceArgs {
+    /// Never fetch tasks more often than every this many seconds
+    #[arg(long = "min-fetch-interval", value_name = "SECS", default_value_t = MIN_FETCH_INTERVAL_SECS)]
+    pub min_fetch_interval: u64,
+
+    /// Never wait longer than this many seconds between successful fetches
+    #[arg(long = "max-fetch-interval", value_name = "SECS", default_value_t = MAX_FETCH_INTERVAL_SECS)]
+    pub max_fetch_interval: u64,
+}
+
+impl FetchCadenceArgs {
+    pub fn bounds(&self) -> CadenceBounds {
+        let min = Duration::from_secs(self.min_fetch_interval);
+        CadenceBounds {
+            min,
+            max: Duration::from_secs(self.max_fetch_interval).max(min),
+        }
+    }
+}
+
+/// Shortest and longest wait between successful fetches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CadenceBounds {
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl Default for CadenceBounds {
+    fn default() -> Self {
+        Self {
+            min: Duration::from_secs(MIN_FETCH_INTERVAL_SECS),
+            max: Duration::from_secs(MAX_FETCH_INTERVAL_SECS),
+        }
+    }
+}
+
+/// Why the node waits before fetching its next task
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WaitReason {
+    /// The orchestrator asked for it with a Retry-After header
+    ServerHint,
+    /// The current task should take about this long to prove
+    ProvingTime(Duration),
+    /// This many recent fetches were rate limited
+    RateLimited(usize),
+    /// Nothing suggests a longer wait than the lower bound
+    MinimumInterval,
+    /// The last fetch failed
+    Retrying,
+}
+
+impl fmt::Display for WaitReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ServerHint => write!(f, "orchestrator requested delay"),
+            Self::ProvingTime(time) => {
+                write!(f, "matching recent proving time of {}s", time.as_secs())
+            }
+            Self::RateLimited(count) => {
+                write!(f, "slowing down after {} rate-limited fetch(es)", count)
+            }
+            Self::MinimumInterval => write!(f, "minimum fetch interval"),
+            Self::Retrying => write!(f, "retrying after a failed fetch"),
+        }
+    }
+}
+
+/// Adapts the wait between fetches to server hints, proving time and rate limiting
+#[derive(Debug)]
+pub struct FetchCadence {
+    bounds: CadenceBounds,
+    proving_time: Option<Duration>,
+    rate_limited: VecDeque<Instant>,
+}
+
+impl FetchCadence {
+    pub fn new(bounds: CadenceBounds) -> Self {
+        Self {
+            bounds,
+            proving_time: None,
+            rate_limited: VecDeque::new(),
+        }
+    }
+
+    /// Fold a finished proof into the running proving time estimate
+    pub fn record_proving_time(&mut self, took: Duration) {
+        self.proving_time = Some(match self.proving_time {
+            Some(average) => {
+                average.mul_f64(1.0 - PROVING_TIME_SMOOTHING) + took.mul_f64(PROVING_TIME_SMOOTHING)
+            }
+            None => took,
+        });
+    }
+
+    /// Remember a fetch the orchestrator rejected as rate limited, returning how many
+    /// rate-limited fetches are remembered
+    pub fn record_rate_limited(&mut self, now: Instant) -> usize {
+        self.rate_limited.push_back(now);
+        self.rate_limited.len()
+    }
+
+    /// How long to wait after a successful fetch at `now`, and why
+    pub fn next_delay(
+        &mut self,
+        server_hint: Option<Duration>,
+        now: Instant,
+    ) -> (Duration, WaitReason) {
+        while let Some(&oldest) = self.rate_limited.front() {
+            if now.saturating_duration_since(oldest) <= rate_limit_memory() {
+                break;
+            }
+            self.rate_limited.pop_front();
+        }
+
+        let (delay, reason) = if let Some(hint) = server_hint {
+            (hint, WaitReason::ServerHint)
+        } else {
+            let (base, reason) = match self.proving_time {
+                Some(time) if time > self.bounds.min => (time, WaitReason::ProvingTime(time)),
+                _ => (self.bounds.min, WaitReason::MinimumInterval),
+            };
+            match self.rate_limited.len() {
+                0 => (base, reason),
+                count => (
+                    base.saturating_mul(1 << count.min(16)),
+                    WaitReason::RateLimited(count),
+                ),
+            }
+        };
+        (delay.clamp(self.bounds.min, self.bounds.max), reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cadence() -> FetchCadence {
+        FetchCadence::new(CadenceBounds {
+            min: Duration::from_secs(10),
+            max: Duration::from_secs(300),
+        })
+    }
+
+    #[test]
+    fn test_delay_follows_proving_time_within_bounds() {
+        let now = Instant::now();
+        let mut cadence = cadence();
+        assert_eq!(
+            cadence.next_delay(None, now),
+            (Duration::from_secs(10), WaitReason::MinimumInterval)
+        );
+
+        cadence.record_proving_time(Duration::from_secs(20));
+        let (delay, reason) = cadence.next_delay(None, now);
+        assert_eq!(delay, Duration::from_secs(20));
+        assert_eq!(reason, WaitReason::ProvingTime(Duration::from_secs(20)));
+
+        // The estimate moves towards newer proofs without jumping to them
+        cadence.record_proving_time(Duration::from_secs(30));
+        let delay = cadence.next_delay(None, now).0.as_secs_f64();
+        assert!((delay - 23.0).abs() < 0.001);
+
+        cadence.record_proving_time(Duration::from_secs(3600));
+        assert_eq!(cadence.next_delay(None, now).0, Duration::from_secs(300));
+    }
+
+    #[test]
+    fn test_server_hint_and_rate_limits() {
+        let now = Instant::now();
+        let mut cadence = cadence();
+        assert_eq!(
+            cadence.next_delay(Some(Duration::from_secs(45)), now),
+            (Duration::from_secs(45), WaitReason::ServerHint)
+        );
+        assert_eq!(
+            cadence.next_delay(Some(Duration::ZERO), now).0,
+            Duration::from_secs(10)
+        );
+
+        cadence.record_rate_limited(now);
+        cadence.record_rate_limited(now);
+        assert_eq!(
+            cadence.next_delay(None, now),
+            (Duration::from_secs(40), WaitReason::RateLimited(2))
+        );
+
+        // Rate limits are forgotten after a while
+        let later = now + rate_limit_memory() + Duration::from_secs(1);
+        assert_eq!(
+            cadence.next_delay(None, later).1,
+            WaitReason::MinimumInterval
+        );
+    }
+}
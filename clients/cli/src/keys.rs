@@ -1,21 +1,77 @@
 //! Ethereum address validation functions.
 
-/// Check if a given string is a valid Ethereum address.
-pub fn is_valid_eth_address(address: &str) -> bool {
+use sha3::{Digest, Keccak256};
+use thiserror::Error;
+
+/// Why a string is not a usable Ethereum address
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AddressError {
+    #[error("expected 42 characters, got {0}")]
+    Length(usize),
+
+    #[error("it must start with '0x'")]
+    MissingPrefix,
+
+    #[error("'{0}' is not a hex digit")]
+    InvalidCharacter(char),
+
+    /// Mixed-case addresses carry an EIP-55 checksum, which catches most typos
+    #[error("the checksum does not match; if the address is right, it is written {expected}")]
+    Checksum { expected: String },
+}
+
+/// Validate an Ethereum address and return it in its canonical, EIP-55 checksummed form.
+///
+/// All-lowercase and all-uppercase addresses carry no checksum and are accepted as they are;
+/// mixed-case addresses must match their checksum.
+pub fn parse_eth_address(address: &str) -> Result<String, AddressError> {
     // Must be 42 characters: "0x" + 40 hex digits
-    if address.len() != 42 {
-        return false;
+    if address.chars().count() != 42 {
+        return Err(AddressError::Length(address.chars().count()));
     }
 
     // Must start with "0x" or "0X"
-    if !address.starts_with("0x") && !address.starts_with("0X") {
-        return false;
-    }
+    let Some(digits) = address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))
+    else {
+        return Err(AddressError::MissingPrefix);
+    };
 
     // Check that the remaining 40 characters are all valid hex digits
-    address[2..].chars().all(|c| c.is_ascii_hexdigit())
+    if let Some(c) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(AddressError::InvalidCharacter(c));
+    }
+
+    let checksummed = to_checksum_address(digits);
+    let has_lower = digits.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = digits.chars().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper && digits != &checksummed[2..] {
+        return Err(AddressError::Checksum {
+            expected: checksummed,
+        });
+    }
+    Ok(checksummed)
+}
 
-    // TODO: validate EIP-55 checksum
+/// EIP-55: a letter is upper-cased when the matching nibble of the Keccak-256 hash of the
+/// lowercase address is 8 or more.
+fn to_checksum_address(digits: &str) -> String {
+    let lower = digits.to_ascii_lowercase();
+    let hash = Keccak256::digest(lower.as_bytes());
+    let checksummed: String = lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("0x{}", checksummed)
 }
 
 #[cfg(test)]
@@ -24,63 +80,73 @@ mod tests {
 
     #[test]
     fn valid_checksum_address() {
-        assert!(is_valid_eth_address(
-            "0x52908400098527886E0F7030069857D2E4169EE7"
-        )); // correct checksum
+        assert!(parse_eth_address("0x52908400098527886E0F7030069857D2E4169EE7").is_ok()); // correct checksum
     }
 
     #[test]
     /// Validation should be case-insensitive for hex digits.
     fn valid_all_lowercase() {
-        assert!(is_valid_eth_address(
-            "0xde709f2102306220921060314715629080e2fb77"
-        ));
+        assert!(parse_eth_address("0xde709f2102306220921060314715629080e2fb77").is_ok());
     }
 
     #[test]
     /// Validation should be case-insensitive for hex digits.
     fn valid_all_uppercase() {
-        assert!(is_valid_eth_address(
-            "0xDE709F2102306220921060314715629080E2FB77"
-        ));
+        assert!(parse_eth_address("0xDE709F2102306220921060314715629080E2FB77").is_ok());
     }
 
     #[test]
     /// Validation should be case-insensitive for prefix "0x".
     fn valid_uppercase_prefix() {
-        assert!(is_valid_eth_address(
-            "0X52908400098527886E0F7030069857D2E4169EE7"
-        ));
+        assert!(parse_eth_address("0X52908400098527886E0F7030069857D2E4169EE7").is_ok());
     }
 
     #[test]
-    #[ignore]
-    /// TODO: Validate EIP-55 checksum
+    /// Mixed-case addresses must match their EIP-55 checksum.
     fn invalid_checksum_address() {
-        assert!(!is_valid_eth_address(
-            "0x52908400098527886E0F7030069857D2E4169ee7"
-        ));
+        assert!(parse_eth_address("0x52908400098527886E0F7030069857D2E4169ee7").is_err());
+        assert_eq!(
+            parse_eth_address("0x52908400098527886E0F7030069857D2E4169ee7"),
+            Err(AddressError::Checksum {
+                expected: "0x52908400098527886E0F7030069857D2E4169EE7".to_string()
+            })
+        );
+    }
+
+    #[test]
+    /// Every accepted spelling of an address normalizes to its checksummed form.
+    fn normalizes_to_checksummed_form() {
+        for address in [
+            "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            "0X5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        ] {
+            assert_eq!(
+                parse_eth_address(address).unwrap(),
+                "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+            );
+        }
+        assert_eq!(
+            parse_eth_address("0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359").unwrap(),
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359"
+        );
     }
 
     #[test]
     /// Address must be exactly 42 characters long.
     fn invalid_length() {
-        assert!(!is_valid_eth_address("0x123")); // too short
+        assert!(parse_eth_address("0x123").is_err()); // too short
     }
 
     #[test]
     /// Check for invalid characters (e.g. non-hex characters) in the address.
     fn invalid_chars() {
-        assert!(!is_valid_eth_address(
-            "0xZ2908400098527886E0F7030069857D2E4169EE7"
-        )); // 'Z' is not hex
+        assert!(parse_eth_address("0xZ2908400098527886E0F7030069857D2E4169EE7").is_err()); // 'Z' is not hex
     }
 
     #[test]
     /// Address must start with "0x" or "0X".
     fn missing_prefix() {
-        assert!(!is_valid_eth_address(
-            "52908400098527886E0F7030069857D2E4169EE7"
-        )); // no 0x
+        assert!(parse_eth_address("52908400098527886E0F7030069857D2E4169EE7").is_err()); // no 0x
     }
 }
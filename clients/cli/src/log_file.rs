@@ -0,0 +1,266 @@
+//! Rotating log file
+//!
+//! `--log-file` installs a [`log`] backend that appends to a file in both TUI and headless
+//! mode. Worker events reach it through [`crate::event_bus::LogSink`], and the proving
+//! subprocess's stderr is logged through it instead of being written over the dashboard.
+//! The file is rotated by size and, optionally, at the start of every hour or day; rotated
+//! files are renamed `<path>.1`, `<path>.2`, ... and only the newest `keep` are retained.
+
+use crate::consts::cli_consts::event_sinks;
+use crate::logging::get_rust_log_level;
+use chrono::{DateTime, Local};
+use clap::{Args, ValueEnum};
+use log::{LevelFilter, Log, Metadata, Record};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Command-line options for the log file
+#[derive(Args, Debug, Clone)]
+pub struct LogFileArgs {
+    /// Also write logs to this file, in both TUI and headless mode (level taken from RUST_LOG)
+    #[arg(long = "log-file", value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+
+    /// Rotate the log file once it reaches this size, in MiB
+    #[arg(long = "log-max-size", value_name = "MIB", default_value_t = event_sinks::FILE_MAX_BYTES / MIB)]
+    pub log_max_size: u64,
+
+    /// Also rotate the log file at the start of every hour or day
+    #[arg(long = "log-rotate", value_enum, value_name = "INTERVAL")]
+    pub log_rotate: Option<RotationInterval>,
+
+    /// Number of rotated log files to keep
+    #[arg(long = "log-keep", value_name = "COUNT", default_value_t = event_sinks::FILE_KEEP)]
+    pub log_keep: usize,
+}
+
+const MIB: u64 = 1024 * 1024;
+
+impl LogFileArgs {
+    /// Install the file logger if `--log-file` was given. Returns whether it was installed.
+    pub fn init(&self) -> Result<bool, std::io::Error> {
+        let Some(path) = &self.log_file else {
+            return Ok(false);
+        };
+        let rotation = Rotation {
+            max_bytes: Some(self.log_max_size.saturating_mul(MIB)).filter(|bytes| *bytes > 0),
+            interval: self.log_rotate,
+        };
+        let file = RotatingFile::open(path.clone(), rotation, self.log_keep)?;
+        let level = LevelFilter::from(get_rust_log_level());
+        let logger: &'static FileLogger = Box::leak(Box::new(FileLogger {
+            file: Mutex::new(file),
+            level,
+        }));
+        log::set_logger(logger)
+            .map_err(|_| std::io::Error::other("a logger is already installed"))?;
+        log::set_max_level(level);
+        Ok(true)
+    }
+}
+
+/// Calendar interval for time-based rotation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RotationInterval {
+    Hourly,
+    Daily,
+}
+
+impl RotationInterval {
+    /// Identifies the period a timestamp falls in; the file rotates when this changes
+    fn period(self, time: DateTime<Local>) -> String {
+        match self {
+            RotationInterval::Hourly => time.format("%Y-%m-%d %H").to_string(),
+            RotationInterval::Daily => time.format("%Y-%m-%d").to_string(),
+        }
+    }
+}
+
+/// When a log file is rotated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rotation {
+    /// Rotate before a write would take the file past this size
+    pub max_bytes: Option<u64>,
+    /// Rotate on the first write in a new hour or day
+    pub interval: Option<RotationInterval>,
+}
+
+/// An append-only file that rotates itself as lines are written
+#[derive(Debug)]
+pub struct RotatingFile {
+    path: PathBuf,
+    rotation: Rotation,
+    keep: usize,
+    file: File,
+    written: u64,
+    period: Option<String>,
+}
+
+impl RotatingFile {
+    pub fn open(path: PathBuf, rotation: Rotation, keep: usize) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = open_append(&path)?;
+        let metadata = file.metadata()?;
+        // An existing file belongs to the period it was last written in
+        let period = rotation.interval.and_then(|interval| {
+            let modified = metadata.modified().ok()?;
+            Some(interval.period(DateTime::<Local>::from(modified)))
+        });
+        Ok(Self {
+            path,
+            rotation,
+            keep,
+            file,
+            written: metadata.len(),
+            period,
+        })
+    }
+
+    pub fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        self.write_line_at(line, Local::now())
+    }
+
+    fn write_line_at(&mut self, line: &str, now: DateTime<Local>) -> std::io::Result<()> {
+        let bytes = line.len() as u64 + 1;
+        let new_period = self.rotation.interval.map(|interval| interval.period(now));
+        let period_changed = new_period.is_some() && self.period != new_period;
+        let too_large = self
+            .rotation
+            .max_bytes
+            .is_some_and(|max| self.written + bytes > max);
+        if self.written > 0 && (period_changed || too_large) {
+            self.rotate()?;
+        }
+        self.period = new_period;
+
+        writeln!(self.file, "{}", line)?;
+        self.written += bytes;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.file.flush()?;
+        if self.keep == 0 {
+            // Nothing to retain: start the file over
+            self.file = File::create(&self.path)?;
+        } else {
+            let _ = fs::remove_file(self.rotated_path(self.keep));
+            for index in (1..self.keep).rev() {
+                let from = self.rotated_path(index);
+                if from.exists() {
+                    fs::rename(&from, self.rotated_path(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated_path(1))?;
+            self.file = open_append(&self.path)?;
+        }
+        self.written = 0;
+        Ok(())
+    }
+}
+
+fn open_append(path: &Path) -> std::io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// [`log`] backend writing to a [`RotatingFile`]
+struct FileLogger {
+    file: Mutex<RotatingFile>,
+    level: LevelFilter,
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = format!(
+            "{} {:<5} {}: {}",
+            Local::now().format("%Y-%m-%d %H:%M:%S"),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.write_line(&line);
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_size_rotation_keeps_limited_history() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("logs").join("nexus.log");
+        let rotation = Rotation {
+            max_bytes: Some(100),
+            interval: None,
+        };
+        let mut file = RotatingFile::open(path.clone(), rotation, 2).unwrap();
+        for i in 0..4 {
+            file.write_line(&format!("{}{}", i, "x".repeat(60)))
+                .unwrap();
+        }
+        file.flush().unwrap();
+
+        let read = |path: PathBuf| fs::read_to_string(path).unwrap();
+        assert!(read(path.clone()).starts_with('3'));
+        assert!(read(file.rotated_path(1)).starts_with('2'));
+        assert!(read(file.rotated_path(2)).starts_with('1'));
+        assert!(!file.rotated_path(3).exists());
+    }
+
+    #[test]
+    fn test_daily_rotation() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("nexus.log");
+        let rotation = Rotation {
+            max_bytes: None,
+            interval: Some(RotationInterval::Daily),
+        };
+        let mut file = RotatingFile::open(path.clone(), rotation, 3).unwrap();
+        let morning = Local.with_ymd_and_hms(2025, 3, 1, 8, 0, 0).unwrap();
+        file.write_line_at("first", morning).unwrap();
+        file.write_line_at("second", morning + chrono::Duration::hours(2))
+            .unwrap();
+        file.write_line_at("next day", morning + chrono::Duration::days(1))
+            .unwrap();
+        file.flush().unwrap();
+
+        assert_eq!(
+            fs::read_to_string(file.rotated_path(1)).unwrap(),
+            "first\nsecond\n"
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "next day\n");
+    }
+}
@@ -1,34 +1,61 @@
 // Copyright (c) 2025 Nexus. All rights reserved.
 
 mod analytics;
+mod atomic_write;
+mod benchmark;
 mod cli_messages;
 mod config;
 mod consts;
+mod daemon;
+mod doctor;
 mod environment;
+mod event_bus;
 mod events;
+mod fetch_cadence;
 mod keys;
+mod log_file;
 mod logging;
 mod network;
 #[path = "proto/nexus.orchestrator.rs"]
 mod nexus_orchestrator;
 mod orchestrator;
+mod proof_archive;
 mod prover;
 mod register;
+mod run_summary;
 mod runtime;
+mod schedule;
 mod session;
+mod setup;
 pub mod system;
 mod task;
+mod task_capture;
+mod thermal;
 mod ui;
 mod version;
 mod workers;
 
+use crate::benchmark::{Calibration, MaxDifficulty, default_max_parallel, run_benchmark};
 use crate::config::{Config, get_config_path};
+use crate::daemon::DaemonArgs;
+use crate::doctor::run_diagnostics;
 use crate::environment::Environment;
+use crate::event_bus::LogSink;
+use crate::fetch_cadence::FetchCadenceArgs;
+use crate::log_file::LogFileArgs;
 use crate::orchestrator::OrchestratorClient;
+use crate::proof_archive::ProofArchiveArgs;
 use crate::prover::engine::ProvingEngine;
 use crate::register::{register_node, register_user};
+use crate::schedule::ScheduleArgs;
 use crate::session::{run_headless_mode, run_tui_mode, setup_session};
+use crate::setup::{NodeRequest, setup};
+use crate::task_capture::{CaptureArgs, TaskCapture, replay};
+use crate::thermal::ThermalArgs;
 use crate::version::manager::validate_version_requirements;
+use crate::version::requirements::RequirementsSource;
+use crate::version::updater::{ReleaseChannel, UpdateOutcome, self_update};
+use crate::workers::core::ProvingOptions;
 use clap::{ArgAction, Parser, Subcommand};
 use postcard::to_allocvec;
 use std::error::Error;
@@ -39,12 +66,18 @@ use std::process::exit;
 #[command(author, version = concat!(env!("CARGO_PKG_VERSION"), " (build ", env!("BUILD_TIMESTAMP"), ")"), about, long_about = None)]
 /// Command-line arguments
 struct Args {
+    /// Config file to use instead of config.json in NEXUS_CONFIG_DIR or ~/.nexus. The node's
+    /// other state is kept next to it.
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<std::path::PathBuf>,
+
     /// Command to execute
     #[command(subcommand)]
     command: Command,
 }
 
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)]
 enum Command {
     /// Start the prover
     Start {
@@ -75,6 +108,48 @@ enum Command {
         /// Maximum number of tasks to process before exiting (default: unlimited)
         #[arg(long = "max-tasks", value_name = "MAX_TASKS")]
         max_tasks: Option<u32>,
+
+        /// Write the end-of-run summary as JSON to this file on exit
+        #[arg(long = "summary-file", value_name = "PATH")]
+        summary_file: Option<std::path::PathBuf>,
+
+        /// Update to the latest stable release and restart if this version is no longer supported
+        #[arg(long = "auto-update", action = ArgAction::SetTrue)]
+        auto_update: bool,
+
+        /// Where to load version requirements from: a URL, a local file path, or `embedded`
+        /// (default: the published requirements, or NEXUS_VERSION_REQUIREMENTS if set)
+        #[arg(long = "version-requirements", value_name = "SOURCE")]
+        version_requirements: Option<String>,
+
+        /// Maximum task difficulty to request; `auto` uses the `bench` calibration
+        #[arg(long = "max-difficulty", value_enum, default_value = "large")]
+        max_difficulty: MaxDifficulty,
+
+        /// Submit proofs without verifying them locally first, for trusted hardware
+        #[arg(long = "skip-local-verify", action = ArgAction::SetTrue)]
+        skip_local_verify: bool,
+
+        #[command(flatten)]
+        keep_proofs: ProofArchiveArgs,
+
+        #[command(flatten)]
+        schedule: ScheduleArgs,
+
+        #[command(flatten)]
+        thermal: ThermalArgs,
+
+        #[command(flatten)]
+        fetch_cadence: FetchCadenceArgs,
+
+        #[command(flatten)]
+        capture: CaptureArgs,
+
+        #[command(flatten)]
+        log_file: LogFileArgs,
+
+        #[command(flatten)]
+        daemon: DaemonArgs,
     },
     /// Register a new user
     RegisterUser {
@@ -88,8 +163,75 @@ enum Command {
         #[arg(long, value_name = "NODE_ID")]
         node_id: Option<u64>,
     },
+    /// Register a user and node in one non-interactive, repeatable step, printing the result
+    /// as JSON
+    Setup {
+        /// User's public Ethereum wallet address. 42-character hex string starting with '0x'
+        #[arg(long, value_name = "WALLET_ADDRESS")]
+        wallet_address: String,
+
+        /// Link this existing node
+        #[arg(long, value_name = "NODE_ID", conflicts_with = "new_node")]
+        node_id: Option<u64>,
+
+        /// Register a new node unless the config already holds one of this wallet
+        #[arg(long = "new-node", action = ArgAction::SetTrue)]
+        new_node: bool,
+
+        /// Directory holding config.json, like NEXUS_CONFIG_DIR
+        #[arg(long = "config-dir", value_name = "DIR")]
+        config_dir: Option<std::path::PathBuf>,
+    },
     /// Clear the node configuration and logout.
     Logout,
+    /// Download, verify and install the latest release of the CLI
+    Update {
+        /// Only check whether an update is available, without installing it
+        #[arg(long, action = ArgAction::SetTrue)]
+        check: bool,
+
+        /// Release channel to update from
+        #[arg(long, value_enum, default_value = "stable")]
+        channel: ReleaseChannel,
+    },
+    /// Diagnose common node setup problems
+    Doctor {
+        /// Node ID to check instead of the one in the config file
+        #[arg(long, value_name = "NODE_ID")]
+        node_id: Option<u64>,
+
+        /// Custom orchestrator URL (overrides environment setting)
+        #[arg(long = "orchestrator-url", value_name = "URL")]
+        orchestrator_url: Option<String>,
+
+        /// Where to load version requirements from: a URL, a local file path, or `embedded`
+        #[arg(long = "version-requirements", value_name = "SOURCE")]
+        version_requirements: Option<String>,
+
+        /// Print the report as JSON, e.g. to attach to a support ticket
+        #[arg(long, action = ArgAction::SetTrue)]
+        json: bool,
+    },
+    /// Measure local proving throughput and save a calibration for this machine
+    Bench {
+        /// Highest number of parallel provers to test (default: as many as cores and memory allow, up to 8)
+        #[arg(long = "max-threads", value_name = "MAX_THREADS")]
+        max_threads: Option<usize>,
+
+        /// Number of times each benchmark input is proven per prover
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        rounds: u32,
+
+        /// Print the results without saving the calibration
+        #[arg(long = "no-save", action = ArgAction::SetTrue)]
+        no_save: bool,
+    },
+    /// Re-prove a task saved by `start --capture-failed` offline, printing every step
+    Replay {
+        /// Capture file to replay
+        #[arg(value_name = "CAPTURE")]
+        capture: std::path::PathBuf,
+    },
     /// Hidden command for subprocess proof generation
     #[command(hide = true, name = "prove-fib-subprocess")]
     ProveFibSubprocess {
@@ -97,6 +239,9 @@ enum Command {
         #[arg(long)]
         inputs: String,
     },
+    /// Hidden command serving proof requests over stdin and stdout for a worker
+    #[command(hide = true, name = "prove-fib-server")]
+    ProveFibServer,
 }
 
 #[tokio::main]
@@ -112,9 +257,12 @@ async fn main() -> Result<(), Box<dyn Error>> {
         .parse::<Environment>()
         .unwrap_or(Environment::default());
 
+    let args = Args::parse();
+    if let Some(path) = args.config {
+        config::select_config_path(path);
+    }
     let config_path = get_config_path()?;
 
-    let args = Args::parse();
     match args.command {
         Command::Start {
             node_id,
@@ -124,15 +272,21 @@ async fn main() -> Result<(), Box<dyn Error>> {
             check_mem,
             with_background,
             max_tasks,
+            summary_file,
+            auto_update,
+            version_requirements,
+            max_difficulty,
+            skip_local_verify,
+            keep_proofs,
+            schedule,
+            thermal,
+            fetch_cadence,
+            capture,
+            log_file,
+            daemon,
         } => {
-            // If a custom orchestrator URL is provided, create a custom environment
-            let final_environment = if let Some(url) = orchestrator_url {
-                Environment::Custom {
-                    orchestrator_url: url,
-                }
-            } else {
-                environment
-            };
+            let final_environment = with_orchestrator_url(environment, orchestrator_url);
+            let requirements_source = resolve_requirements_source(version_requirements);
             start(
                 node_id,
                 final_environment,
@@ -142,22 +296,158 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 check_mem,
                 with_background,
                 max_tasks,
+                summary_file,
+                auto_update,
+                requirements_source,
+                max_difficulty,
+                skip_local_verify,
+                keep_proofs,
+                schedule,
+                thermal,
+                fetch_cadence,
+                capture,
+                log_file,
+                daemon,
             )
             .await
         }
         Command::Logout => {
-            print_cmd_info!("Logging out", "Clearing node configuration file...");
+            print_cmd_info!(
+                "Logging out",
+                "Clearing node configuration file {}...",
+                config_path.display()
+            );
             Config::clear_node_config(&config_path).map_err(Into::into)
         }
+        Command::Update { check, channel } => {
+            print_cmd_info!("Checking for updates", "Channel: {:?}", channel);
+            match self_update(env!("CARGO_PKG_VERSION"), channel, check).await? {
+                UpdateOutcome::UpToDate { latest } => {
+                    print_cmd_success!(
+                        "Already up to date",
+                        "Current version: {} (latest: {})",
+                        env!("CARGO_PKG_VERSION"),
+                        latest
+                    );
+                }
+                UpdateOutcome::Available {
+                    latest,
+                    release_url,
+                } => {
+                    print_cmd_info!(
+                        "Update available",
+                        "Version {} is available (current: {}). Run `nexus-network update` to install it, or visit {}",
+                        latest,
+                        env!("CARGO_PKG_VERSION"),
+                        release_url
+                    );
+                }
+                UpdateOutcome::Updated { latest } => {
+                    print_cmd_success!(
+                        "Update complete",
+                        "Updated from {} to {}",
+                        env!("CARGO_PKG_VERSION"),
+                        latest
+                    );
+                }
+            }
+            Ok(())
+        }
+        Command::Doctor {
+            node_id,
+            orchestrator_url,
+            version_requirements,
+            json,
+        } => {
+            let orchestrator =
+                OrchestratorClient::new(with_orchestrator_url(environment, orchestrator_url));
+            let report = run_diagnostics(
+                node_id,
+                &config_path,
+                &orchestrator,
+                &resolve_requirements_source(version_requirements),
+                &config_path.with_file_name("version_requirements.json"),
+            )
+            .await;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                report.print();
+            }
+            if report.has_failures() {
+                exit(1);
+            }
+            Ok(())
+        }
+        Command::Bench {
+            max_threads,
+            rounds,
+            no_save,
+        } => {
+            let max_parallel = max_threads.unwrap_or_else(default_max_parallel).max(1);
+            let calibration = run_benchmark(max_parallel, rounds as usize).await?;
+            calibration.print_summary();
+            if !no_save {
+                let path = Calibration::path(&config_path);
+                calibration.save(&path)?;
+                print_cmd_info!("Calibration saved", "{}", path.display());
+            }
+            Ok(())
+        }
         Command::RegisterUser { wallet_address } => {
             print_cmd_info!("Registering user", "Wallet address: {}", wallet_address);
             let orchestrator = Box::new(OrchestratorClient::new(environment));
             register_user(&wallet_address, &config_path, orchestrator).await
         }
+        Command::Setup {
+            wallet_address,
+            node_id,
+            new_node,
+            config_dir,
+        } => {
+            let config_path =
+                config_dir.map_or(config_path, |dir| dir.join(config::CONFIG_FILE_NAME));
+            let node = match (node_id, new_node) {
+                (Some(node_id), _) => NodeRequest::Link(node_id),
+                (None, true) => NodeRequest::Ensure,
+                (None, false) => NodeRequest::Keep,
+            };
+            let orchestrator = OrchestratorClient::new(environment);
+            match setup(&wallet_address, node, &config_path, &orchestrator).await {
+                Ok(report) => {
+                    println!("{}", serde_json::to_string(&report)?);
+                    Ok(())
+                }
+                Err(e) => {
+                    println!("{}", e.to_json());
+                    exit(e.exit_code());
+                }
+            }
+        }
         Command::RegisterNode { node_id } => {
             let orchestrator = Box::new(OrchestratorClient::new(environment));
             register_node(node_id, &config_path, orchestrator).await
         }
+        Command::Replay { capture } => {
+            let capture = TaskCapture::load(&capture).unwrap_or_else(|e| {
+                print_cmd_error!("Replay failed", &e.to_string());
+                exit(1);
+            });
+            match replay(&capture).await {
+                Ok(result) => {
+                    print_cmd_success!(
+                        "Replay succeeded",
+                        "The task proved and verified; combined hash {}",
+                        result.combined_hash
+                    );
+                    Ok(())
+                }
+                Err(e) => {
+                    print_cmd_error!("Replay failed", &e.to_string());
+                    exit(1);
+                }
+            }
+        }
         Command::ProveFibSubprocess { inputs } => {
             let inputs: (u32, u32, u32) = serde_json::from_str(&inputs)?;
             match ProvingEngine::prove_fib_subprocess(&inputs) {
@@ -173,9 +463,38 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 }
             }
         }
+        Command::ProveFibServer => {
+            let stdin = std::io::stdin().lock();
+            let stdout = std::io::stdout().lock();
+            if let Err(e) = prover::child::serve(stdin, stdout) {
+                eprintln!("{}", e);
+                exit(consts::cli_consts::SUBPROCESS_INTERNAL_ERROR_CODE);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// If a custom orchestrator URL is provided, create a custom environment
+fn with_orchestrator_url(
+    environment: Environment,
+    orchestrator_url: Option<String>,
+) -> Environment {
+    match orchestrator_url {
+        Some(url) => Environment::Custom {
+            orchestrator_url: url,
+        },
+        None => environment,
     }
 }
 
+/// Version requirements source from the command line, then NEXUS_VERSION_REQUIREMENTS
+fn resolve_requirements_source(arg: Option<String>) -> RequirementsSource {
+    arg.or_else(|| std::env::var("NEXUS_VERSION_REQUIREMENTS").ok())
+        .map(|source| RequirementsSource::from(source.as_str()))
+        .unwrap_or_default()
+}
+
 /// Starts the Nexus CLI application.
 ///
 /// # Arguments
@@ -187,6 +506,18 @@ async fn main() -> Result<(), Box<dyn Error>> {
 /// * `check_mem` - Whether to check risky memory usage.
 /// * `with_background` - Whether to use the alternate TUI background color.
 /// * `max_tasks` - Optional maximum number of tasks to prove.
+/// * `summary_file` - Where to write the end-of-run summary as JSON.
+/// * `auto_update` - Whether to self-update and restart when the version is blocked.
+/// * `requirements_source` - Where to load version requirements from.
+/// * `max_difficulty` - Maximum task difficulty to request.
+/// * `skip_local_verify` - Whether to submit proofs without verifying them locally.
+/// * `keep_proofs` - Where and how many generated proofs to keep.
+/// * `schedule` - When to prove, overriding the config file.
+/// * `thermal` - Temperature and load limits, overriding the config file.
+/// * `fetch_cadence` - Bounds of the wait between task fetches.
+/// * `capture` - Where to save tasks that fail to prove.
+/// * `log_file` - Where to write a copy of the log.
+/// * `daemon` - PID file and health endpoint for headless mode.
 #[allow(clippy::too_many_arguments)]
 async fn start(
     node_id: Option<u64>,
@@ -197,21 +528,102 @@ async fn start(
     check_mem: bool,
     with_background: bool,
     max_tasks: Option<u32>,
+    summary_file: Option<std::path::PathBuf>,
+    auto_update: bool,
+    requirements_source: RequirementsSource,
+    max_difficulty: MaxDifficulty,
+    skip_local_verify: bool,
+    keep_proofs: ProofArchiveArgs,
+    schedule: ScheduleArgs,
+    thermal: ThermalArgs,
+    fetch_cadence: FetchCadenceArgs,
+    capture: CaptureArgs,
+    log_file: LogFileArgs,
+    daemon: DaemonArgs,
 ) -> Result<(), Box<dyn Error>> {
+    // 0. File logging, so everything after this point can be reviewed later
+    let log_to_file = log_file.init().inspect_err(|e| {
+        print_cmd_error!("Failed to open log file", &e.to_string());
+    })?;
+
     // 1. Version checking (will internally perform country detection without race)
-    validate_version_requirements().await?;
+    let requirements_cache_path = config_path.with_file_name("version_requirements.json");
+    validate_version_requirements(&requirements_source, &requirements_cache_path, auto_update)
+        .await?;
 
     // 2. Configuration resolution
     let orchestrator_client = OrchestratorClient::new(env.clone());
-    let config = Config::resolve(node_id, &config_path, &orchestrator_client).await?;
+    let config = Config::resolve(node_id, &config_path, &orchestrator_client)
+        .await
+        .unwrap_or_else(|e| exit_with_config_error(e));
 
     // 3. Session setup (authenticated worker only)
-    let session = setup_session(config, env, check_mem, max_threads, max_tasks).await?;
+    let calibration = Calibration::load(&Calibration::path(&config_path));
+    if max_difficulty == MaxDifficulty::Auto && calibration.is_none() {
+        print_cmd_warn!(
+            "No calibration found",
+            "Run `nexus-network bench` to calibrate `--max-difficulty auto`. Requesting large tasks."
+        );
+    }
+    let max_difficulty = max_difficulty.resolve(calibration.as_ref());
+    if skip_local_verify {
+        print_cmd_warn!(
+            "Local verification disabled",
+            "Proofs will be submitted without being verified on this machine first."
+        );
+    }
+    let schedule = schedule.apply(config.schedule.clone());
+    let thermal = thermal.apply(config.thermal.clone());
+    let mut session = setup_session(
+        config,
+        env,
+        check_mem,
+        max_threads,
+        max_tasks,
+        max_difficulty,
+        calibration,
+        ProvingOptions {
+            skip_local_verify,
+            proof_archive: keep_proofs.archive(),
+            schedule,
+            thermal,
+            fetch_cadence: fetch_cadence.bounds(),
+            capture_failed: capture.capture_failed,
+        },
+    )
+    .await
+    .unwrap_or_else(|e| exit_with_config_error(e));
+    if log_to_file {
+        session.events.add_sink(Box::new(LogSink::new()));
+    }
 
     // 4. Run appropriate mode
+    let run_stats = session.run_stats.clone();
     if headless {
-        run_headless_mode(session).await
+        run_headless_mode(session, &daemon).await?;
     } else {
-        run_tui_mode(session, with_background).await
+        run_tui_mode(session, with_background).await?;
     }
+
+    // 5. Report how the run went, and exit with a code telling batch runs why it ended
+    let summary = run_stats
+        .lock()
+        .map_err(|_| "run statistics unavailable")?
+        .summary(std::time::Instant::now(), max_tasks.is_some());
+    summary.print();
+    if let Some(path) = summary_file {
+        summary.write(&path).inspect_err(|e| {
+            print_cmd_error!("Failed to write run summary", &e.to_string());
+        })?;
+    }
+    if summary.exit_code != consts::cli_consts::run_exit_codes::COMPLETED {
+        exit(summary.exit_code);
+    }
+    Ok(())
+}
+
+/// Report a configuration or authentication error and exit with its exit code
+fn exit_with_config_error(e: Box<dyn Error>) -> ! {
+    print_cmd_error!("Configuration error", &e.to_string());
+    exit(consts::cli_consts::run_exit_codes::CONFIG);
 }
@@ -0,0 +1,89 @@
+//! Exponential backoff with full jitter
+//!
+//! Each consecutive failure doubles the upper bound of the retry delay, up to a cap, and the
+//! actual delay is drawn uniformly between zero and that bound. Randomizing the whole delay
+//! keeps large numbers of nodes from retrying in lockstep after an outage.
+
+use rand::Rng;
+use std::time::Duration;
+
+/// Exponential backoff state for a sequence of consecutive failures
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    initial: Duration,
+    max: Duration,
+    attempt: u32,
+}
+
+impl Backoff {
+    pub fn new(initial: Duration, max: Duration) -> Self {
+        Self {
+            initial,
+            max,
+            attempt: 0,
+        }
+    }
+
+    /// Upper bound of the next delay: `initial * 2^attempt`, capped at `max`
+    pub fn ceiling(&self) -> Duration {
+        let factor = 2u32.saturating_pow(self.attempt);
+        self.initial.saturating_mul(factor).min(self.max)
+    }
+
+    /// Draw the next delay and advance to the next attempt
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = jitter(self.ceiling());
+        self.attempt = self.attempt.saturating_add(1);
+        delay
+    }
+
+    /// Start over after a successful request
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+/// A uniformly random duration between zero and `max`, inclusive
+pub fn jitter(max: Duration) -> Duration {
+    let max_ms = u64::try_from(max.as_millis()).unwrap_or(u64::MAX);
+    Duration::from_millis(rand::thread_rng().gen_range(0..=max_ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ceiling_doubles_up_to_cap() {
+        let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(10));
+        let ceilings: Vec<u64> = (0..6)
+            .map(|_| {
+                let ceiling = backoff.ceiling().as_secs();
+                backoff.next_delay();
+                ceiling
+            })
+            .collect();
+        assert_eq!(ceilings, vec![1, 2, 4, 8, 10, 10]);
+
+        backoff.reset();
+        assert_eq!(backoff.ceiling(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_next_delay_stays_within_ceiling() {
+        let mut backoff = Backoff::new(Duration::from_millis(100), Duration::from_secs(1));
+        for _ in 0..50 {
+            let ceiling = backoff.ceiling();
+            assert!(backoff.next_delay() <= ceiling);
+        }
+    }
+
+    #[test]
+    fn test_ceiling_does_not_overflow() {
+        let mut backoff = Backoff::new(Duration::from_secs(120), Duration::from_secs(600));
+        for _ in 0..100 {
+            backoff.next_delay();
+        }
+        assert_eq!(backoff.ceiling(), Duration::from_secs(600));
+    }
+}
@@ -0,0 +1,140 @@
+//! Circuit breaker for orchestrator requests
+//!
+//! After a run of consecutive outage-like failures the circuit opens and no requests are sent
+//! until a cooldown has passed. The circuit then goes half-open and lets a single probe
+//! through: success closes it again, failure re-opens it for another cooldown.
+
+use super::backoff::jitter;
+use std::time::{Duration, Instant};
+
+/// State of the circuit breaker
+#[derive(Debug, Copy, Clone, Eq, PartialEq, strum::Display)]
+pub enum CircuitState {
+    /// Requests flow normally
+    Closed,
+    /// Requests are held back until the cooldown expires
+    Open,
+    /// A single probe request is allowed through
+    #[strum(to_string = "Half-open")]
+    HalfOpen,
+}
+
+/// Tracks consecutive failures and decides whether requests may be sent
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    consecutive_failures: u32,
+    state: CircuitState,
+    open_until: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold,
+            cooldown,
+            consecutive_failures: 0,
+            state: CircuitState::Closed,
+            open_until: None,
+        }
+    }
+
+    pub fn state(&self) -> CircuitState {
+        self.state
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+
+    /// Time left before an open circuit allows a probe
+    pub fn remaining_cooldown(&self) -> Duration {
+        match (self.state, self.open_until) {
+            (CircuitState::Open, Some(until)) => until.saturating_duration_since(Instant::now()),
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Whether a request may be sent now. An open circuit whose cooldown has expired
+    /// moves to half-open and allows the probe.
+    pub fn allow_request(&mut self) -> bool {
+        if self.state == CircuitState::Open {
+            if self.remaining_cooldown() > Duration::ZERO {
+                return false;
+            }
+            self.state = CircuitState::HalfOpen;
+        }
+        true
+    }
+
+    /// Record a request that reached a healthy orchestrator
+    pub fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.state = CircuitState::Closed;
+        self.open_until = None;
+    }
+
+    /// Record an outage-like failure, opening the circuit if the threshold is reached
+    /// or the half-open probe failed
+    pub fn record_failure(&mut self) {
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        if self.state == CircuitState::HalfOpen
+            || self.consecutive_failures >= self.failure_threshold
+        {
+            self.state = CircuitState::Open;
+            // Spread probes out so nodes that tripped together do not probe together
+            self.open_until = Some(Instant::now() + self.cooldown + jitter(self.cooldown / 4));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opens_after_threshold() {
+        let mut breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+        breaker.record_failure();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.allow_request());
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!breaker.allow_request());
+        assert!(breaker.remaining_cooldown() >= Duration::from_secs(59));
+    }
+
+    #[test]
+    fn test_success_resets_failure_count() {
+        let mut breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(breaker.consecutive_failures(), 1);
+    }
+
+    #[test]
+    fn test_half_open_probe() {
+        let mut breaker = CircuitBreaker::new(1, Duration::ZERO);
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // Cooldown has elapsed, so the next request is a probe
+        assert!(breaker.allow_request());
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        // A failed probe re-opens the circuit
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // A successful probe closes it
+        assert!(breaker.allow_request());
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(breaker.consecutive_failures(), 0);
+    }
+}
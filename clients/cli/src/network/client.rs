@@ -1,15 +1,20 @@
 //! Network client with built-in retry and error handling
 
+use super::backoff::Backoff;
+use super::circuit_breaker::{CircuitBreaker, CircuitState};
 use super::error_handler::ErrorHandler;
 use super::request_timer::RequestTimer;
+use super::retry_policy::{ErrorClass, RetryPolicy, server_retry_delay};
 use crate::consts::cli_consts;
+use crate::events::{Event, Worker, format_duration};
 use crate::logging::LogLevel;
 use crate::orchestrator::Orchestrator;
 use crate::orchestrator::error::OrchestratorError;
 use crate::task::Task;
+use crate::workers::core::EventSender;
 use ed25519_dalek::{SigningKey, VerifyingKey};
 
-use std::{cmp::min, time::Duration};
+use tokio::time::sleep;
 
 /// Proof submission data grouped by business concern
 #[derive(Debug, Clone)]
@@ -50,22 +55,54 @@ impl ProofSubmission {
     }
 }
 
+/// How the orchestrator acknowledged a proof submission
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    /// The proof was accepted
+    Accepted,
+    /// The orchestrator already had a proof for this task
+    AlreadySubmitted,
+}
+
 /// Network client with built-in retry and request timing
+///
+/// Which errors are retried is decided by the operation's [`RetryPolicy`]. Failed requests
+/// are retried after a jittered exponential backoff, or after the server-provided
+/// Retry-After delay if there is one. Sustained outages open a circuit breaker that holds
+/// back requests until a half-open probe succeeds.
 pub struct NetworkClient {
     error_handler: ErrorHandler,
     request_timer: RequestTimer,
-    max_retries: u32,
+    policy: RetryPolicy,
+    backoff: Backoff,
+    circuit_breaker: CircuitBreaker,
+    /// Where to report circuit breaker state changes, and as which worker
+    events: Option<(EventSender, Worker)>,
+    reported_circuit_state: CircuitState,
 }
 
 impl NetworkClient {
-    pub fn new(request_timer: RequestTimer, max_retries: u32) -> Self {
+    pub fn new(request_timer: RequestTimer, policy: RetryPolicy) -> Self {
         Self {
             error_handler: ErrorHandler::new(),
             request_timer,
-            max_retries,
+            backoff: policy.backoff(),
+            policy,
+            circuit_breaker: CircuitBreaker::new(
+                cli_consts::circuit_breaker::FAILURE_THRESHOLD,
+                cli_consts::circuit_breaker::cooldown(),
+            ),
+            events: None,
+            reported_circuit_state: CircuitState::Closed,
         }
     }
 
+    /// Report circuit breaker state changes as events from the given worker
+    pub fn with_event_sender(mut self, event_sender: EventSender, worker: Worker) -> Self {
+        self.events = Some((event_sender, worker));
+        self
+    }
+
     /// Fetch a task with automatic retry and server-controlled timing
     pub async fn fetch_task(
         &mut self,
@@ -76,48 +113,42 @@ impl NetworkClient {
         let mut attempts = 0;
 
         loop {
+            self.wait_for_circuit().await;
+
             // Make the request
             match orchestrator.get_proof_task(node_id, verifying_key).await {
                 Ok(task) => {
-                    self.request_timer.record_success();
+                    self.record_success().await;
                     return Ok(task);
                 }
                 Err(e) => {
                     attempts += 1;
-
-                    // Get server-provided retry delay and record failure
-                    let server_retry_delay = e
-                        .get_retry_after_seconds()
-                        .map(|secs| Duration::from_secs(secs as u64))
-                        .map(|delay| {
-                            min(
-                                delay + cli_consts::rate_limiting::extra_retry_delay(),
-                                Duration::from_secs(60 * 10),
-                            )
-                        });
-                    self.request_timer.record_failure(server_retry_delay);
+                    self.record_failure(&e).await;
 
                     // Check if we should retry
-                    if attempts >= self.max_retries || !self.error_handler.should_retry(&e) {
+                    if attempts >= self.policy.max_retries || !self.policy.should_retry(&e) {
                         return Err(e);
                     }
+                    self.wait_for_timer().await;
                 }
             }
         }
     }
 
     /// Submit a proof with automatic retry and server-controlled timing
-    /// Returns Ok(attempts) on success or Err((error, attempts)) on failure
+    /// Returns Ok((outcome, attempts)) on success or Err((error, attempts)) on failure
     pub async fn submit_proof(
         &mut self,
         orchestrator: &dyn Orchestrator,
         submission: ProofSubmission,
         signing_key: SigningKey,
         num_provers: usize,
-    ) -> Result<u32, (OrchestratorError, u32)> {
+    ) -> Result<(SubmissionOutcome, u32), (OrchestratorError, u32)> {
         let mut attempts = 0;
 
         loop {
+            self.wait_for_circuit().await;
+
             // Make the request
             match orchestrator
                 .submit_proof(
@@ -134,33 +165,103 @@ impl NetworkClient {
             {
                 Ok(()) => {
                     attempts += 1;
-                    self.request_timer.record_success();
-                    return Ok(attempts);
+                    self.record_success().await;
+                    return Ok((SubmissionOutcome::Accepted, attempts));
+                }
+                Err(e) if self.policy.classify(&e) == ErrorClass::AlreadyDone => {
+                    // e.g. an earlier attempt succeeded but its response was lost
+                    attempts += 1;
+                    self.record_success().await;
+                    return Ok((SubmissionOutcome::AlreadySubmitted, attempts));
                 }
                 Err(e) => {
                     attempts += 1;
-
-                    // Get server-provided retry delay and record failure
-                    let server_retry_delay = e
-                        .get_retry_after_seconds()
-                        .map(|secs| Duration::from_secs(secs as u64))
-                        .map(|delay| {
-                            min(
-                                delay + cli_consts::rate_limiting::extra_retry_delay(),
-                                Duration::from_secs(60 * 10),
-                            )
-                        });
-                    self.request_timer.record_failure(server_retry_delay);
+                    self.record_failure(&e).await;
 
                     // Check if we should retry
-                    if attempts >= self.max_retries || !self.error_handler.should_retry(&e) {
+                    if attempts >= self.policy.max_retries || !self.policy.should_retry(&e) {
                         return Err((e, attempts));
                     }
+                    self.wait_for_timer().await;
                 }
             }
         }
     }
 
+    /// Record a successful request: reset backoff and close the circuit
+    async fn record_success(&mut self) {
+        self.request_timer.record_success();
+        self.backoff.reset();
+        self.circuit_breaker.record_success();
+        self.report_circuit_state().await;
+    }
+
+    /// Record a failed request and schedule the next attempt
+    async fn record_failure(&mut self, error: &OrchestratorError) {
+        // Server-provided delays win over the local backoff
+        let mut retry_delay =
+            server_retry_delay(error).unwrap_or_else(|| self.backoff.next_delay());
+
+        if self.error_handler.indicates_outage(error) {
+            self.circuit_breaker.record_failure();
+            retry_delay = retry_delay.max(self.circuit_breaker.remaining_cooldown());
+        } else {
+            // The orchestrator answered, so it is up even though the request failed
+            self.circuit_breaker.record_success();
+        }
+
+        self.request_timer.record_failure(Some(retry_delay));
+        self.report_circuit_state().await;
+    }
+
+    /// Wait until the request timer allows the next attempt
+    async fn wait_for_timer(&mut self) {
+        while !self.request_timer.can_proceed() {
+            sleep(self.request_timer.time_until_next()).await;
+        }
+    }
+
+    /// Wait while the circuit is open, then let the half-open probe through
+    async fn wait_for_circuit(&mut self) {
+        while !self.circuit_breaker.allow_request() {
+            sleep(self.circuit_breaker.remaining_cooldown()).await;
+        }
+        self.report_circuit_state().await;
+    }
+
+    /// Send an event if the circuit breaker state changed since the last report
+    async fn report_circuit_state(&mut self) {
+        let state = self.circuit_breaker.state();
+        if state == self.reported_circuit_state {
+            return;
+        }
+        self.reported_circuit_state = state;
+
+        if let Some((event_sender, worker)) = &self.events {
+            let msg = match state {
+                CircuitState::Open => format!(
+                    "Orchestrator unavailable after {} consecutive failures; pausing requests for {}",
+                    self.circuit_breaker.consecutive_failures(),
+                    format_duration(self.circuit_breaker.remaining_cooldown())
+                ),
+                CircuitState::HalfOpen => {
+                    "Probing orchestrator availability with a single request".to_string()
+                }
+                CircuitState::Closed => {
+                    "Orchestrator available again; resuming requests".to_string()
+                }
+            };
+            event_sender
+                .send_event(Event::circuit_state_change(*worker, state, msg))
+                .await;
+        }
+    }
+
+    /// Classify an error according to this client's retry policy
+    pub fn error_class(&self, error: &OrchestratorError) -> ErrorClass {
+        self.policy.classify(error)
+    }
+
     /// Get error classification for logging
     pub fn classify_error(&self, error: &OrchestratorError) -> LogLevel {
         self.error_handler.classify_error(error)
@@ -171,3 +272,120 @@ impl NetworkClient {
         &mut self.request_timer
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::RequestTimerConfig;
+    use crate::network::retry_policy::Operation;
+    use crate::orchestrator::MockOrchestrator;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    fn server_error() -> OrchestratorError {
+        OrchestratorError::Http {
+            status: 503,
+            message: "Service unavailable".to_string(),
+            headers: HashMap::new(),
+        }
+    }
+
+    fn fast_client(operation: Operation, max_retries: u32) -> NetworkClient {
+        let timer = RequestTimer::new(RequestTimerConfig::combined(
+            Duration::ZERO,
+            1000,
+            Duration::from_secs(60),
+            Duration::from_millis(1),
+        ));
+        let policy = RetryPolicy {
+            operation,
+            max_retries,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+        };
+        NetworkClient::new(timer, policy)
+    }
+
+    fn test_task() -> Task {
+        Task::new(
+            "task".to_string(),
+            "fib_input_initial".to_string(),
+            vec![],
+            crate::nexus_orchestrator::TaskType::ProofRequired,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_fetch_task_retries_after_backoff() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = calls.clone();
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator.expect_get_proof_task().returning(move |_, _| {
+            match counter.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Err(server_error()),
+                _ => Ok(test_task()),
+            }
+        });
+
+        let mut client = fast_client(Operation::FetchTask, 5);
+        let key = ed25519_dalek::SigningKey::from_bytes(&[1; 32]).verifying_key();
+        let task = client.fetch_task(&orchestrator, "1", key).await.unwrap();
+        assert_eq!(task.task_id, "task");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_opens_and_reports_state() {
+        let (sender, mut receiver) = mpsc::channel(10);
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator
+            .expect_get_proof_task()
+            .returning(|_, _| Err(server_error()));
+
+        let mut client = fast_client(Operation::FetchTask, 2)
+            .with_event_sender(EventSender::new(sender), Worker::TaskFetcher);
+        client.circuit_breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+        let key = ed25519_dalek::SigningKey::from_bytes(&[1; 32]).verifying_key();
+        assert!(client.fetch_task(&orchestrator, "1", key).await.is_err());
+        assert_eq!(client.circuit_breaker.state(), CircuitState::Open);
+
+        let event = receiver.try_recv().unwrap();
+        assert_eq!(event.circuit_state, Some(CircuitState::Open));
+        assert_eq!(event.log_level, LogLevel::Warn);
+
+        // The request timer holds the next fetch back for the cooldown
+        assert!(client.request_timer_mut().time_until_next() > Duration::from_secs(59));
+    }
+
+    #[tokio::test]
+    async fn test_submit_conflict_counts_as_already_submitted() {
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator
+            .expect_submit_proof()
+            .times(1)
+            .returning(|_, _, _, _, _, _, _, _| {
+                Err(OrchestratorError::Http {
+                    status: 409,
+                    message: "Proof already submitted".to_string(),
+                    headers: HashMap::new(),
+                })
+            });
+
+        let mut client = fast_client(Operation::SubmitProof, 5);
+        let submission = ProofSubmission::new(
+            "task".to_string(),
+            "hash".to_string(),
+            vec![],
+            crate::nexus_orchestrator::TaskType::ProofHash,
+        );
+        let key = ed25519_dalek::SigningKey::from_bytes(&[1; 32]);
+        let result = client
+            .submit_proof(&orchestrator, submission, key, 1)
+            .await
+            .unwrap();
+        assert_eq!(result, (SubmissionOutcome::AlreadySubmitted, 1));
+    }
+}
@@ -1,7 +1,7 @@
 //! Centralized error handling and classification
 
 use crate::logging::LogLevel;
-use crate::orchestrator::error::OrchestratorError;
+use crate::orchestrator::error::{OrchestratorError, ServerError};
 
 /// Centralized error handler for all network operations
 #[derive(Debug, Clone)]
@@ -14,46 +14,35 @@ impl ErrorHandler {
 
     /// Classify error and determine appropriate log level
     pub fn classify_error(&self, error: &OrchestratorError) -> LogLevel {
-        match error {
+        match error.server_error() {
             // Rate limiting - low priority
-            OrchestratorError::Http { status, .. } if *status == 429 => LogLevel::Debug,
+            Some(ServerError::RateLimited) => LogLevel::Debug,
 
             // Server errors - temporary issues
-            OrchestratorError::Http { status, .. } if (500..=599).contains(status) => {
-                LogLevel::Warn
-            }
-
-            // Authentication errors - critical
-            OrchestratorError::Http { status, .. } if *status == 401 => LogLevel::Error,
-            OrchestratorError::Http { status, .. } if *status == 403 => LogLevel::Error,
+            Some(ServerError::Unavailable) => LogLevel::Warn,
 
-            // Network issues - usually temporary
-            OrchestratorError::Reqwest(_) => LogLevel::Warn,
+            // Authentication and compatibility errors - critical, need user action
+            Some(
+                ServerError::Unauthorized
+                | ServerError::InvalidSignature
+                | ServerError::UnsupportedVersion,
+            ) => LogLevel::Error,
 
-            // Other errors
+            // Network issues and other errors - usually temporary
             _ => LogLevel::Warn,
         }
     }
 
-    /// Determine if an error should trigger retry logic
-    pub fn should_retry(&self, error: &OrchestratorError) -> bool {
+    /// Determine if an error suggests the orchestrator is unavailable or overloaded,
+    /// as opposed to rejecting this particular request
+    pub fn indicates_outage(&self, error: &OrchestratorError) -> bool {
         match error {
-            // Retry on network/connection errors
             OrchestratorError::Reqwest(_) => true,
-            OrchestratorError::Decode(_) => true,
-
-            // HTTP errors - check status code
-            OrchestratorError::Http { status, .. } => {
-                match *status {
-                    // Don't retry client errors (except rate limiting)
-                    429 => false,      // Rate limiting - don't retry
-                    400..=499 => true, // Other client errors - should retry
-                    // Retry server errors
-                    500..=599 => true,
-                    // Don't retry other status codes
-                    _ => false,
-                }
-            }
+            OrchestratorError::Http { .. } => matches!(
+                error.server_error(),
+                Some(ServerError::RateLimited | ServerError::Unavailable)
+            ),
+            OrchestratorError::Decode(_) => false,
         }
     }
 }
@@ -1,6 +1,11 @@
+pub mod backoff;
+pub mod circuit_breaker;
 pub mod client;
 pub mod error_handler;
 pub mod request_timer;
+pub mod retry_policy;
 
-pub use client::{NetworkClient, ProofSubmission};
+pub use circuit_breaker::CircuitState;
+pub use client::{NetworkClient, ProofSubmission, SubmissionOutcome};
 pub use request_timer::{RequestTimer, RequestTimerConfig};
+pub use retry_policy::{ErrorClass, Operation, RetryPolicy, retry};
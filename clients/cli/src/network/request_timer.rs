@@ -127,6 +127,11 @@ impl RequestTimer {
         }
     }
 
+    /// Hold the next request back for `delay` from now, replacing any pending wait
+    pub fn delay_next(&mut self, delay: Duration) {
+        self.server_retry_until = Some(Instant::now() + delay);
+    }
+
     /// Record a failed request with optional server-provided retry delay
     /// If server_retry_delay is provided, it overrides all other timing logic
     pub fn record_failure(&mut self, server_retry_delay: Option<Duration>) {
@@ -0,0 +1,334 @@
+//! Declarative retry policy per orchestrator operation
+//!
+//! Each operation has a small table of rules mapping errors to an [`ErrorClass`], consulted
+//! before a table of rules shared by all operations. The first matching rule wins, and
+//! anything unmatched is treated as permanent.
+
+use super::backoff::{Backoff, jitter};
+use crate::consts::cli_consts::{proof_submission, rate_limiting, registration, task_fetching};
+use crate::orchestrator::error::{OrchestratorError, ServerError};
+use std::cmp::min;
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::sleep;
+
+/// Orchestrator operations that have their own retry behaviour
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    FetchTask,
+    SubmitProof,
+    Register,
+    GetNode,
+    GetUser,
+}
+
+/// How an error from the orchestrator should be handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+    /// Will not succeed on retry, e.g. a malformed or unauthorized request
+    Permanent,
+    /// Temporary failure; retry after a backoff
+    Transient,
+    /// The server asked us to slow down; retry after its Retry-After delay, or a backoff
+    Throttled,
+    /// The work is already done, e.g. the proof was already submitted; treat as success
+    AlreadyDone,
+    /// The work is no longer wanted, e.g. the task expired; drop it without retrying
+    Obsolete,
+}
+
+/// Which errors a rule applies to
+#[derive(Debug, Clone, Copy)]
+enum ErrorMatch {
+    /// Connection, timeout and other transport errors
+    Network,
+    /// Response body could not be decoded
+    Decode,
+    /// A single HTTP status
+    Status(u16),
+    /// An inclusive range of HTTP statuses
+    StatusRange(u16, u16),
+    /// An error the orchestrator identified by name
+    Server(ServerError),
+}
+
+impl ErrorMatch {
+    fn matches(self, error: &OrchestratorError) -> bool {
+        match (self, error) {
+            (ErrorMatch::Network, OrchestratorError::Reqwest(_)) => true,
+            (ErrorMatch::Decode, OrchestratorError::Decode(_)) => true,
+            (ErrorMatch::Status(expected), OrchestratorError::Http { status, .. }) => {
+                *status == expected
+            }
+            (ErrorMatch::StatusRange(low, high), OrchestratorError::Http { status, .. }) => {
+                (low..=high).contains(status)
+            }
+            (ErrorMatch::Server(expected), error) => error.server_error() == Some(expected),
+            _ => false,
+        }
+    }
+}
+
+struct Rule {
+    matches: ErrorMatch,
+    class: ErrorClass,
+}
+
+const fn rule(matches: ErrorMatch, class: ErrorClass) -> Rule {
+    Rule { matches, class }
+}
+
+/// Rules shared by all operations, consulted after the operation's own rules
+const COMMON_RULES: &[Rule] = &[
+    rule(
+        ErrorMatch::Server(ServerError::TaskExpired),
+        ErrorClass::Obsolete,
+    ),
+    rule(
+        ErrorMatch::Server(ServerError::ProofAlreadySubmitted),
+        ErrorClass::AlreadyDone,
+    ),
+    rule(
+        ErrorMatch::Server(ServerError::RateLimited),
+        ErrorClass::Throttled,
+    ),
+    rule(
+        ErrorMatch::Server(ServerError::UnsupportedVersion),
+        ErrorClass::Permanent,
+    ),
+    rule(
+        ErrorMatch::Server(ServerError::InvalidSignature),
+        ErrorClass::Permanent,
+    ),
+    rule(ErrorMatch::Network, ErrorClass::Transient),
+    rule(ErrorMatch::Decode, ErrorClass::Transient),
+    rule(ErrorMatch::Status(408), ErrorClass::Transient), // Request Timeout
+    rule(ErrorMatch::Status(429), ErrorClass::Throttled), // Too Many Requests
+    rule(ErrorMatch::StatusRange(500, 599), ErrorClass::Transient),
+    // 400, 401, 403, 404, 422, ... will not succeed on retry
+    rule(ErrorMatch::StatusRange(400, 499), ErrorClass::Permanent),
+];
+
+const SUBMIT_PROOF_RULES: &[Rule] = &[
+    rule(ErrorMatch::Status(409), ErrorClass::AlreadyDone), // Conflict: already submitted
+    rule(ErrorMatch::Status(410), ErrorClass::Obsolete),    // Gone: task expired
+];
+
+const NO_RULES: &[Rule] = &[];
+
+/// Retry limits and error classification for one operation
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub operation: Operation,
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub fn for_operation(operation: Operation) -> Self {
+        let (max_retries, initial_backoff, max_backoff) = match operation {
+            Operation::FetchTask => (
+                task_fetching::MAX_RETRIES,
+                task_fetching::initial_backoff(),
+                task_fetching::max_backoff(),
+            ),
+            Operation::SubmitProof => (
+                proof_submission::MAX_RETRIES,
+                proof_submission::initial_backoff(),
+                proof_submission::max_backoff(),
+            ),
+            Operation::Register | Operation::GetNode | Operation::GetUser => (
+                registration::MAX_RETRIES,
+                registration::initial_backoff(),
+                registration::max_backoff(),
+            ),
+        };
+        Self {
+            operation,
+            max_retries,
+            initial_backoff,
+            max_backoff,
+        }
+    }
+
+    fn rules(&self) -> &'static [Rule] {
+        match self.operation {
+            Operation::SubmitProof => SUBMIT_PROOF_RULES,
+            Operation::FetchTask
+            | Operation::Register
+            | Operation::GetNode
+            | Operation::GetUser => NO_RULES,
+        }
+    }
+
+    /// Classify an error using the operation's rules, then the common rules
+    pub fn classify(&self, error: &OrchestratorError) -> ErrorClass {
+        self.rules()
+            .iter()
+            .chain(COMMON_RULES)
+            .find(|rule| rule.matches.matches(error))
+            .map_or(ErrorClass::Permanent, |rule| rule.class)
+    }
+
+    /// Whether the request should be retried after this error
+    pub fn should_retry(&self, error: &OrchestratorError) -> bool {
+        matches!(
+            self.classify(error),
+            ErrorClass::Transient | ErrorClass::Throttled
+        )
+    }
+
+    /// A fresh backoff sequence for this operation
+    pub fn backoff(&self) -> Backoff {
+        Backoff::new(self.initial_backoff, self.max_backoff)
+    }
+}
+
+/// Delay requested by the server, with jitter so clients do not all return at once
+pub fn server_retry_delay(error: &OrchestratorError) -> Option<Duration> {
+    error
+        .get_retry_after_seconds()
+        .map(|secs| Duration::from_secs(secs as u64))
+        .map(|delay| {
+            min(
+                delay + jitter(rate_limiting::extra_retry_delay()),
+                rate_limiting::max_server_retry_delay(),
+            )
+        })
+}
+
+/// Run `request` until it succeeds, fails with an error the policy does not retry,
+/// or runs out of attempts
+pub async fn retry<T, F, Fut>(policy: &RetryPolicy, mut request: F) -> Result<T, OrchestratorError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, OrchestratorError>>,
+{
+    let mut backoff = policy.backoff();
+    let mut attempts = 0;
+    loop {
+        match request().await {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                attempts += 1;
+                if attempts >= policy.max_retries || !policy.should_retry(&e) {
+                    return Err(e);
+                }
+                let delay = server_retry_delay(&e).unwrap_or_else(|| backoff.next_delay());
+                sleep(delay).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn http(status: u16) -> OrchestratorError {
+        OrchestratorError::Http {
+            status,
+            message: String::new(),
+            headers: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_classification_table() {
+        let fetch = RetryPolicy::for_operation(Operation::FetchTask);
+        let submit = RetryPolicy::for_operation(Operation::SubmitProof);
+        let cases = [
+            (&fetch, 400, ErrorClass::Permanent),
+            (&fetch, 401, ErrorClass::Permanent),
+            (&fetch, 403, ErrorClass::Permanent),
+            (&fetch, 404, ErrorClass::Permanent),
+            (&fetch, 408, ErrorClass::Transient),
+            (&fetch, 409, ErrorClass::Permanent),
+            (&fetch, 429, ErrorClass::Throttled),
+            (&fetch, 503, ErrorClass::Transient),
+            (&fetch, 302, ErrorClass::Permanent),
+            (&submit, 409, ErrorClass::AlreadyDone),
+            (&submit, 410, ErrorClass::Obsolete),
+            (&submit, 429, ErrorClass::Throttled),
+            (&submit, 400, ErrorClass::Permanent),
+        ];
+        for (policy, status, expected) in cases {
+            assert_eq!(
+                policy.classify(&http(status)),
+                expected,
+                "{:?} {}",
+                policy.operation,
+                status
+            );
+        }
+    }
+
+    #[test]
+    fn test_named_server_errors_take_precedence_over_status() {
+        let fetch = RetryPolicy::for_operation(Operation::FetchTask);
+        let body = |name: &str| OrchestratorError::Http {
+            status: 500,
+            message: format!(r#"{{"name":"{}","message":""}}"#, name),
+            headers: HashMap::new(),
+        };
+        assert_eq!(
+            fetch.classify(&body("UnsupportedVersion")),
+            ErrorClass::Permanent
+        );
+        assert_eq!(fetch.classify(&body("TaskExpired")), ErrorClass::Obsolete);
+        assert_eq!(
+            fetch.classify(&body("ProofAlreadySubmitted")),
+            ErrorClass::AlreadyDone
+        );
+        assert_eq!(
+            fetch.classify(&body("SomethingElse")),
+            ErrorClass::Transient
+        );
+    }
+
+    #[test]
+    fn test_should_retry_throttled_but_not_permanent() {
+        let policy = RetryPolicy::for_operation(Operation::GetNode);
+        assert!(policy.should_retry(&http(429)));
+        assert!(policy.should_retry(&http(502)));
+        assert!(!policy.should_retry(&http(404)));
+    }
+
+    #[tokio::test]
+    async fn test_retry_stops_on_permanent_error() {
+        let policy = RetryPolicy {
+            operation: Operation::Register,
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+        };
+
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = retry(&policy, || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(http(503)),
+                _ => Err(http(400)),
+            }
+        })
+        .await;
+        assert!(matches!(
+            result,
+            Err(OrchestratorError::Http { status: 400, .. })
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let calls = AtomicU32::new(0);
+        let result = retry(&policy, || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Err(http(500)),
+                _ => Ok("node"),
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), "node");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+}
@@ -2,15 +2,17 @@
 //!
 //! A client for the Nexus Orchestrator, allowing for proof task retrieval and submission.
 
+use crate::benchmark::{Calibration, effective_gflops};
 use crate::environment::Environment;
 use crate::nexus_orchestrator::{
     GetProofTaskRequest, GetProofTaskResponse, NodeType, RegisterNodeRequest, RegisterNodeResponse,
     RegisterUserRequest, SubmitProofRequest, TaskDifficulty, UserResponse,
 };
 use crate::orchestrator::Orchestrator;
-use crate::orchestrator::error::OrchestratorError;
-use crate::system::{estimate_peak_gflops, get_memory_info};
+use crate::orchestrator::error::{OrchestratorError, parse_retry_after};
+use crate::system::get_memory_info;
 use crate::task::Task;
+use chrono::Utc;
 use ed25519_dalek::{Signer, SigningKey, VerifyingKey};
 use prost::Message;
 use reqwest::{Client, ClientBuilder, Response};
@@ -46,6 +48,8 @@ pub(crate) static COUNTRY_CODE: OnceLock<String> = OnceLock::new();
 pub struct OrchestratorClient {
     client: Client,
     environment: Environment,
+    max_difficulty: TaskDifficulty,
+    calibration: Option<Calibration>,
 }
 
 impl OrchestratorClient {
@@ -57,9 +61,28 @@ impl OrchestratorClient {
                 .build()
                 .expect("Failed to create HTTP client"),
             environment,
+            max_difficulty: TaskDifficulty::Large,
+            calibration: None,
         }
     }
 
+    /// Set the maximum difficulty of tasks requested from the orchestrator (default: large)
+    pub fn with_max_difficulty(mut self, max_difficulty: TaskDifficulty) -> Self {
+        self.max_difficulty = max_difficulty;
+        self
+    }
+
+    /// Set the benchmark calibration used for the GFLOP/s reported with each proof
+    pub fn with_calibration(mut self, calibration: Option<Calibration>) -> Self {
+        self.calibration = calibration;
+        self
+    }
+
+    /// Maximum difficulty of tasks requested from the orchestrator
+    pub fn max_difficulty(&self) -> TaskDifficulty {
+        self.max_difficulty
+    }
+
     /// Public accessor for privacy-preserving country code (cached during run)
     #[allow(dead_code)]
     pub async fn country(&self) -> String {
@@ -150,6 +173,16 @@ impl OrchestratorClient {
         endpoint: &str,
         body: Vec<u8>,
     ) -> Result<T, OrchestratorError> {
+        let (response, _) = self.post_request_with_retry_after(endpoint, body).await?;
+        Ok(response)
+    }
+
+    /// Like `post_request`, also returning the Retry-After hint of a successful response
+    async fn post_request_with_retry_after<T: Message + Default>(
+        &self,
+        endpoint: &str,
+        body: Vec<u8>,
+    ) -> Result<(T, Option<Duration>), OrchestratorError> {
         let url = self.build_url(endpoint);
         let response = self
             .client
@@ -162,8 +195,14 @@ impl OrchestratorClient {
             .await?;
 
         let response = Self::handle_response_status(response).await?;
+        let retry_after = response
+            .headers()
+            .get("retry-after")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| parse_retry_after(value, Utc::now()))
+            .map(|secs| Duration::from_secs(secs as u64));
         let response_bytes = response.bytes().await?;
-        Self::decode_response(&response_bytes)
+        Ok((Self::decode_response(&response_bytes)?, retry_after))
     }
 
     async fn post_request_no_response(
@@ -383,11 +422,16 @@ impl Orchestrator for OrchestratorClient {
             node_id: node_id.to_string(),
             node_type: NodeType::CliProver as i32,
             ed25519_public_key: verifying_key.to_bytes().to_vec(),
-            max_difficulty: TaskDifficulty::Large as i32,
+            max_difficulty: self.max_difficulty as i32,
         };
         let request_bytes = Self::encode_request(&request);
-        let response: GetProofTaskResponse = self.post_request("v3/tasks", request_bytes).await?;
-        Ok(Task::from(&response))
+        let (response, retry_after): (GetProofTaskResponse, _) = self
+            .post_request_with_retry_after("v3/tasks", request_bytes)
+            .await?;
+        Ok(Task {
+            retry_after,
+            ..Task::from(&response)
+        })
     }
 
     async fn submit_proof(
@@ -402,7 +446,7 @@ impl Orchestrator for OrchestratorClient {
         individual_proof_hashes: &[String],
     ) -> Result<(), OrchestratorError> {
         let (program_memory, total_memory) = get_memory_info();
-        let flops = estimate_peak_gflops(num_provers);
+        let flops = effective_gflops(self.calibration.as_ref(), num_provers);
         let (signature, public_key) = self.create_signature(&signing_key, task_id, proof_hash);
 
         // Detect country for network optimization (privacy-preserving: only country code, no precise location)
@@ -1,16 +1,107 @@
 //! Error handling for the orchestrator module
 
+use chrono::{DateTime, Utc};
 use prost::DecodeError;
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 use std::collections::HashMap;
 use thiserror::Error;
 
-#[allow(non_snake_case)] // used for json parsing
-#[derive(Serialize, Deserialize)]
+/// JSON error body returned by the orchestrator
+#[derive(Deserialize)]
 struct RawError {
     name: String,
     message: String,
-    httpCode: u16,
+}
+
+/// Known errors reported by the orchestrator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+pub enum ServerError {
+    #[strum(to_string = "Node not found")]
+    NodeNotFound,
+    #[strum(to_string = "User not found")]
+    UserNotFound,
+    #[strum(to_string = "Task not found")]
+    TaskNotFound,
+    #[strum(to_string = "Not found")]
+    NotFound,
+    #[strum(to_string = "Wallet already registered")]
+    WalletAlreadyRegistered,
+    #[strum(to_string = "Task expired")]
+    TaskExpired,
+    #[strum(to_string = "Proof already submitted")]
+    ProofAlreadySubmitted,
+    #[strum(to_string = "Rate limited")]
+    RateLimited,
+    #[strum(to_string = "Unsupported CLI version")]
+    UnsupportedVersion,
+    #[strum(to_string = "Invalid signature")]
+    InvalidSignature,
+    #[strum(to_string = "Unauthorized")]
+    Unauthorized,
+    #[strum(to_string = "Invalid request")]
+    InvalidRequest,
+    #[strum(to_string = "Orchestrator unavailable")]
+    Unavailable,
+    #[strum(to_string = "Unknown error")]
+    Unknown,
+}
+
+impl ServerError {
+    /// Decode an error from its name in the response body, falling back to the status code
+    /// when the name is missing or not one we know
+    pub fn decode(status: u16, body: &str) -> Self {
+        serde_json::from_str::<RawError>(body)
+            .ok()
+            .and_then(|raw| Self::from_name(&raw.name))
+            .unwrap_or_else(|| Self::from_status(status))
+    }
+
+    /// Match an error name such as `NodeNotFoundError` or `node_not_found`
+    fn from_name(name: &str) -> Option<Self> {
+        let normalized: String = name
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect::<String>()
+            .to_ascii_lowercase();
+        let normalized = normalized.strip_suffix("error").unwrap_or(&normalized);
+        let error = match normalized {
+            "nodenotfound" => Self::NodeNotFound,
+            "usernotfound" => Self::UserNotFound,
+            "tasknotfound" => Self::TaskNotFound,
+            "notfound" => Self::NotFound,
+            "walletalreadyregistered" | "useralreadyexists" => Self::WalletAlreadyRegistered,
+            "taskexpired" => Self::TaskExpired,
+            "proofalreadysubmitted" | "duplicatesubmission" => Self::ProofAlreadySubmitted,
+            "ratelimited" | "ratelimitexceeded" | "toomanyrequests" => Self::RateLimited,
+            "unsupportedversion" | "versiontooold" | "clientversionunsupported" => {
+                Self::UnsupportedVersion
+            }
+            "invalidsignature" => Self::InvalidSignature,
+            "unauthorized" | "forbidden" => Self::Unauthorized,
+            "invalidrequest" | "badrequest" | "validation" => Self::InvalidRequest,
+            _ => return None,
+        };
+        Some(error)
+    }
+
+    fn from_status(status: u16) -> Self {
+        match status {
+            400 | 422 => Self::InvalidRequest,
+            401 | 403 => Self::Unauthorized,
+            404 => Self::NotFound,
+            429 => Self::RateLimited,
+            500..=599 => Self::Unavailable,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Whether the requested resource does not exist
+    pub fn is_not_found(self) -> bool {
+        matches!(
+            self,
+            Self::NodeNotFound | Self::UserNotFound | Self::TaskNotFound | Self::NotFound
+        )
+    }
 }
 
 #[derive(Debug, Error)]
@@ -20,11 +111,11 @@ pub enum OrchestratorError {
     Decode(#[from] DecodeError),
 
     /// Reqwest error, typically related to network issues or request failures.
-    #[error("Reqwest error: {0}")]
+    #[error("Network error: {}", describe_network_error(.0))]
     Reqwest(#[from] reqwest::Error),
 
     /// An error occurred while processing the request.
-    #[error("HTTP error with status {status}: {message}")]
+    #[error("{} (HTTP {status}){}", ServerError::decode(*.status, .message), detail_suffix(.message))]
     Http {
         status: u16,
         message: String,
@@ -57,42 +148,70 @@ impl OrchestratorError {
     }
 
     /// Get the Retry-After header value in seconds, if present
+    ///
+    /// Both forms are accepted: a number of seconds, or an HTTP date. Server errors without a
+    /// Retry-After header fall back to the client's jittered backoff.
     pub fn get_retry_after_seconds(&self) -> Option<u32> {
         match self {
-            Self::Http {
-                status, headers, ..
-            } => {
-                if (500..=599).contains(status) {
-                    return Some(30);
-                }
-                headers
-                    .get("retry-after")
-                    .and_then(|value| value.parse::<u32>().ok())
-            }
+            Self::Http { headers, .. } => headers
+                .get("retry-after")
+                .and_then(|value| parse_retry_after(value, Utc::now())),
             _ => None,
         }
     }
 
-    pub fn to_pretty(&self) -> Option<String> {
+    /// The decoded server error, for HTTP errors
+    pub fn server_error(&self) -> Option<ServerError> {
         match self {
             Self::Http {
-                status: _,
-                message: msg,
-                headers: _,
-            } => {
-                if let Ok(parsed) = serde_json::from_str::<RawError>(msg) {
-                    if let Ok(stringified) = serde_json::to_string_pretty(&parsed) {
-                        return Some(stringified);
-                    }
-                }
-
-                None
-            }
+                status, message, ..
+            } => Some(ServerError::decode(*status, message)),
             _ => None,
         }
     }
 }
 
+/// The message from a JSON error body, or the raw body if it is short plain text
+fn server_detail(body: &str) -> Option<String> {
+    if let Ok(raw) = serde_json::from_str::<RawError>(body) {
+        return Some(raw.message).filter(|message| !message.is_empty());
+    }
+    let body = body.trim();
+    let is_plain_text =
+        !body.is_empty() && body.len() <= 200 && !body.starts_with('<') && !body.starts_with('{');
+    is_plain_text.then(|| body.to_string())
+}
+
+fn detail_suffix(body: &str) -> String {
+    server_detail(body)
+        .map(|detail| format!(": {}", detail))
+        .unwrap_or_default()
+}
+
+fn describe_network_error(error: &reqwest::Error) -> &'static str {
+    if error.is_timeout() {
+        "request timed out"
+    } else if error.is_connect() {
+        "could not connect to the orchestrator"
+    } else if error.is_decode() || error.is_body() {
+        "could not read the response"
+    } else {
+        "request failed"
+    }
+}
+
+/// Parse a Retry-After value given as delay-seconds or as an HTTP date relative to `now`.
+/// Dates in the past mean the request may be retried immediately.
+pub(crate) fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<u32> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u32>() {
+        return Some(seconds);
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    let seconds = (date.with_timezone(&Utc) - now).num_seconds().max(0);
+    Some(u32::try_from(seconds).unwrap_or(u32::MAX))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,4 +254,83 @@ mod tests {
 
         assert_eq!(error.get_retry_after_seconds(), None);
     }
+
+    #[test]
+    fn test_get_retry_after_seconds_server_error_without_header() {
+        let error = OrchestratorError::Http {
+            status: 503,
+            message: "Service unavailable".to_string(),
+            headers: HashMap::new(),
+        };
+
+        assert_eq!(error.get_retry_after_seconds(), None);
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date() {
+        let now = DateTime::parse_from_rfc2822("Wed, 21 Oct 2015 07:28:00 GMT")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:30:00 GMT", now),
+            Some(120)
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now),
+            Some(0)
+        );
+        assert_eq!(parse_retry_after(" 45 ", now), Some(45));
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn test_server_error_decoded_from_name() {
+        let body = r#"{"name":"TaskExpiredError","message":"Task 42 has expired","httpCode":410}"#;
+        assert_eq!(ServerError::decode(410, body), ServerError::TaskExpired);
+        assert_eq!(
+            ServerError::decode(400, r#"{"name":"unsupported_version","message":""}"#),
+            ServerError::UnsupportedVersion
+        );
+        assert_eq!(
+            ServerError::decode(404, r#"{"name":"NodeNotFound","message":"no such node"}"#),
+            ServerError::NodeNotFound
+        );
+    }
+
+    #[test]
+    fn test_server_error_falls_back_to_status() {
+        assert_eq!(
+            ServerError::decode(404, "User not found"),
+            ServerError::NotFound
+        );
+        assert_eq!(
+            ServerError::decode(429, r#"{"name":"SomethingNew","message":"slow down"}"#),
+            ServerError::RateLimited
+        );
+        assert_eq!(ServerError::decode(502, "<html>"), ServerError::Unavailable);
+        assert_eq!(ServerError::decode(302, ""), ServerError::Unknown);
+    }
+
+    #[test]
+    fn test_http_error_display_uses_server_message() {
+        let error = OrchestratorError::Http {
+            status: 410,
+            message:
+                r#"{"name":"TaskExpiredError","message":"Task 42 has expired","httpCode":410}"#
+                    .to_string(),
+            headers: HashMap::new(),
+        };
+        assert_eq!(error.server_error(), Some(ServerError::TaskExpired));
+        assert_eq!(
+            error.to_string(),
+            "Task expired (HTTP 410): Task 42 has expired"
+        );
+
+        let error = OrchestratorError::Http {
+            status: 503,
+            message: "<html><body>Service Unavailable</body></html>".to_string(),
+            headers: HashMap::new(),
+        };
+        assert_eq!(error.to_string(), "Orchestrator unavailable (HTTP 503)");
+    }
 }
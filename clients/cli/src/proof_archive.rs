@@ -0,0 +1,331 @@
+//! Proof archive
+//!
+//! `--keep-proofs <dir>` keeps a copy of every generated proof, including those of hash-only
+//! tasks whose proofs are never sent to the orchestrator, so they can be produced later for
+//! a dispute, a spot audit or a re-submission. Each task is written as a bundle directory
+//! named `<timestamp>-<task id>` holding one postcard-encoded `proof-<n>.bin` per input and
+//! a `manifest.json` describing the task, its inputs, the guest program and the hashes. The
+//! oldest bundles are removed once the archive exceeds its count or size limit.
+
+use crate::prover::ProverResult;
+use crate::prover::engine::FIB_INPUT_INITIAL_ELF;
+use crate::prover::input::InputParser;
+use crate::task::Task;
+use chrono::Utc;
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Version of the bundle layout, recorded in every manifest
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const MIB: u64 = 1024 * 1024;
+
+/// Command-line options for the proof archive
+#[derive(Args, Debug, Clone)]
+pub struct ProofArchiveArgs {
+    /// Keep every generated proof in this directory, one bundle per task
+    #[arg(long = "keep-proofs", value_name = "DIR")]
+    pub keep_proofs: Option<PathBuf>,
+
+    /// Keep at most this many task bundles, removing the oldest first
+    #[arg(
+        long = "keep-proofs-count",
+        value_name = "COUNT",
+        requires = "keep_proofs"
+    )]
+    pub keep_proofs_count: Option<usize>,
+
+    /// Keep at most this many MiB of bundles, removing the oldest first
+    #[arg(
+        long = "keep-proofs-size",
+        value_name = "MIB",
+        requires = "keep_proofs"
+    )]
+    pub keep_proofs_size: Option<u64>,
+}
+
+impl ProofArchiveArgs {
+    /// The archive selected on the command line, if any
+    pub fn archive(&self) -> Option<ProofArchive> {
+        let dir = self.keep_proofs.clone()?;
+        Some(ProofArchive::new(
+            dir,
+            Retention {
+                max_bundles: self.keep_proofs_count,
+                max_bytes: self.keep_proofs_size.map(|mib| mib.saturating_mul(MIB)),
+            },
+        ))
+    }
+}
+
+/// How many bundles the archive keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Retention {
+    pub max_bundles: Option<usize>,
+    pub max_bytes: Option<u64>,
+}
+
+/// Description of a task bundle, written as its `manifest.json`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BundleManifest {
+    pub format_version: u32,
+    pub cli_version: String,
+    /// RFC 3339 time the bundle was written
+    pub created_at: String,
+    pub task_id: String,
+    pub program_id: String,
+    /// Orchestrator task type, e.g. `PROOF_HASH`
+    pub task_type: String,
+    /// Keccak-256 of the guest program the proofs were generated with
+    pub elf_keccak256: String,
+    /// The hash submitted for the task
+    pub combined_hash: String,
+    pub proofs: Vec<BundleProof>,
+}
+
+/// One proven input of a task bundle
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BundleProof {
+    /// Public input bytes as sent by the orchestrator, hex-encoded
+    pub public_input_hex: String,
+    /// The decoded fibonacci inputs, when the input has that form
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inputs: Option<(u32, u32, u32)>,
+    /// Keccak-256 of the postcard-encoded proof
+    pub proof_hash: String,
+    /// File in the bundle holding the postcard-encoded proof
+    pub proof_file: String,
+}
+
+/// Directory of task bundles
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofArchive {
+    dir: PathBuf,
+    retention: Retention,
+}
+
+impl ProofArchive {
+    pub fn new(dir: PathBuf, retention: Retention) -> Self {
+        Self { dir, retention }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Write the bundle for a proven task, then apply retention. Returns the bundle path.
+    pub fn save(&self, task: &Task, result: &ProverResult) -> io::Result<PathBuf> {
+        let proofs = result
+            .proofs
+            .iter()
+            .map(postcard::to_allocvec)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(io::Error::other)?;
+        self.write_bundle(
+            task,
+            &proofs,
+            &result.combined_hash,
+            &result.individual_proof_hashes,
+        )
+    }
+
+    fn write_bundle(
+        &self,
+        task: &Task,
+        proofs: &[Vec<u8>],
+        combined_hash: &str,
+        proof_hashes: &[String],
+    ) -> io::Result<PathBuf> {
+        fs::create_dir_all(&self.dir)?;
+        let now = Utc::now();
+        let name = format!(
+            "{}-{}",
+            now.format("%Y%m%dT%H%M%S%.3fZ"),
+            sanitize(&task.task_id)
+        );
+        let bundle = self.dir.join(&name);
+        // Written under a temporary name so a partial bundle is never mistaken for a whole one
+        let partial = self.dir.join(format!(".{}.partial", name));
+        fs::create_dir_all(&partial)?;
+
+        let mut entries = Vec::with_capacity(proofs.len());
+        for (index, proof) in proofs.iter().enumerate() {
+            let proof_file = format!("proof-{}.bin", index);
+            fs::write(partial.join(&proof_file), proof)?;
+            let public_input = task.all_inputs().get(index).cloned().unwrap_or_default();
+            entries.push(BundleProof {
+                public_input_hex: public_input.iter().map(|b| format!("{:02x}", b)).collect(),
+                inputs: InputParser::parse_triple_input(&public_input).ok(),
+                proof_hash: proof_hashes.get(index).cloned().unwrap_or_default(),
+                proof_file,
+            });
+        }
+
+        let manifest = BundleManifest {
+            format_version: BUNDLE_FORMAT_VERSION,
+            cli_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: now.to_rfc3339(),
+            task_id: task.task_id.clone(),
+            program_id: task.program_id.clone(),
+            task_type: task.task_type.as_str_name().to_string(),
+            elf_keccak256: format!("{:x}", Keccak256::digest(FIB_INPUT_INITIAL_ELF)),
+            combined_hash: combined_hash.to_string(),
+            proofs: entries,
+        };
+        let json = serde_json::to_string_pretty(&manifest)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(partial.join(MANIFEST_FILE), json)?;
+        fs::rename(&partial, &bundle)?;
+
+        self.prune(&bundle)?;
+        Ok(bundle)
+    }
+
+    /// Remove the oldest bundles until the archive is within its limits, always keeping `newest`
+    fn prune(&self, newest: &Path) -> io::Result<()> {
+        let mut bundles = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.join(MANIFEST_FILE).is_file() {
+                bundles.push((path.clone(), dir_size(&path)?));
+            }
+        }
+        // Names start with the time they were written, so they sort oldest first
+        bundles.sort();
+
+        let mut count = bundles.len();
+        let mut bytes: u64 = bundles.iter().map(|(_, size)| size).sum();
+        for (path, size) in bundles {
+            let over_count = self.retention.max_bundles.is_some_and(|max| count > max);
+            let over_size = self.retention.max_bytes.is_some_and(|max| bytes > max);
+            if !(over_count || over_size) || path == newest {
+                break;
+            }
+            fs::remove_dir_all(&path)?;
+            count -= 1;
+            bytes -= size;
+        }
+        Ok(())
+    }
+}
+
+fn dir_size(dir: &Path) -> io::Result<u64> {
+    let mut size = 0;
+    for entry in fs::read_dir(dir)? {
+        size += entry?.metadata()?.len();
+    }
+    Ok(size)
+}
+
+/// Keep a task ID safe to use in a file name
+pub(crate) fn sanitize(task_id: &str) -> String {
+    task_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nexus_orchestrator::TaskType;
+    use tempfile::tempdir;
+
+    fn save(archive: &ProofArchive, task_id: &str) -> PathBuf {
+        let mut input = Vec::new();
+        for value in [10u32, 1, 1] {
+            input.extend_from_slice(&value.to_le_bytes());
+        }
+        let task = Task::new(
+            task_id.to_string(),
+            "fib_input_initial".to_string(),
+            input,
+            TaskType::ProofHash,
+        );
+        archive
+            .write_bundle(
+                &task,
+                &[vec![1, 2, 3]],
+                "combined",
+                &["individual".to_string()],
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_bundle_is_self_describing() {
+        let dir = tempdir().unwrap();
+        let archive = ProofArchive::new(dir.path().to_path_buf(), Retention::default());
+        let bundle = save(&archive, "task/1");
+        assert!(
+            bundle
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .ends_with("-task_1")
+        );
+
+        let manifest: BundleManifest =
+            serde_json::from_slice(&fs::read(bundle.join(MANIFEST_FILE)).unwrap()).unwrap();
+        assert_eq!(manifest.task_id, "task/1");
+        assert_eq!(manifest.task_type, "PROOF_HASH");
+        assert_eq!(manifest.combined_hash, "combined");
+        assert_eq!(manifest.proofs[0].inputs, Some((10, 1, 1)));
+        assert_eq!(
+            manifest.proofs[0].public_input_hex,
+            "0a0000000100000001000000"
+        );
+        assert_eq!(manifest.proofs[0].proof_hash, "individual");
+        assert_eq!(
+            fs::read(bundle.join(&manifest.proofs[0].proof_file)).unwrap(),
+            [1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn test_retention_removes_oldest_bundles() {
+        let dir = tempdir().unwrap();
+        let archive = ProofArchive::new(
+            dir.path().to_path_buf(),
+            Retention {
+                max_bundles: Some(2),
+                max_bytes: None,
+            },
+        );
+        let bundles: Vec<PathBuf> = (0..3)
+            .map(|i| {
+                let bundle = save(&archive, &format!("task-{}", i));
+                // Bundle names have millisecond resolution
+                std::thread::sleep(std::time::Duration::from_millis(5));
+                bundle
+            })
+            .collect();
+
+        assert!(!bundles[0].exists());
+        assert!(bundles[1].exists());
+        assert!(bundles[2].exists());
+
+        // A size limit smaller than one bundle still keeps the newest
+        let tiny = ProofArchive::new(
+            dir.path().to_path_buf(),
+            Retention {
+                max_bundles: None,
+                max_bytes: Some(1),
+            },
+        );
+        let newest = save(&tiny, "task-3");
+        assert!(newest.exists());
+        assert!(!bundles[2].exists());
+    }
+}
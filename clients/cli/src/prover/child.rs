@@ -0,0 +1,412 @@
+//! Persistent prover child
+//!
+//! Workers prove inside a long-lived `nexus-network prove-fib-server` child rather than
+//! starting a process per input, which keeps proving memory isolated from the node (an
+//! out-of-memory kill takes down the child, not the worker) without paying process start-up
+//! for every input. Requests and responses are postcard-encoded frames over the child's
+//! stdin and stdout, each prefixed with its length as a little-endian `u32`. The child is
+//! replaced after a fixed number of proofs or once its memory has grown too far. A
+//! [`ProverPool`] holds one child per input that can be proven at once.
+
+use super::engine::{ProvingEngine, StderrTail, SubprocessOutput, read_stderr, subprocess_failure};
+use super::types::ProverError;
+use crate::consts::cli_consts::{
+    PROJECTED_MEMORY_REQUIREMENT, PROVER_CHILD_EXIT_TIMEOUT_SECS, PROVER_CHILD_MAX_FRAME_BYTES,
+    PROVER_CHILD_MAX_PROOFS, PROVER_CHILD_MAX_RSS_GROWTH, PROVER_MAX_SLOTS,
+    PROVER_SLOT_MEMORY_HEADROOM,
+};
+use crate::system;
+use crate::workers::core::EventSender;
+use nexus_sdk::stwo::seq::Proof;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::io::{ErrorKind, Read, Write};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::process::{Child, ChildStdin, ChildStdout};
+use tokio::task::JoinHandle;
+
+/// Request sent to the prover child
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProveRequest {
+    pub inputs: (u32, u32, u32),
+}
+
+/// Response from the prover child
+#[derive(Serialize, Deserialize, Debug)]
+pub enum ProveResponse {
+    Proof(Proof),
+    /// Proving failed without taking the child down
+    Error(String),
+}
+
+/// Child side: answer requests from `input` on `output` until `input` is closed
+pub fn serve(mut input: impl Read, mut output: impl Write) -> Result<(), ProverError> {
+    // Parse the guest program once, not for every input
+    let program = ProvingEngine::create_fib_prover().map_err(|e| e.to_string());
+    while let Some(request) = read_frame::<ProveRequest>(&mut input)? {
+        let proved = program.as_ref().map_err(Clone::clone).and_then(|program| {
+            ProvingEngine::prove_fib_reusing(program, &request.inputs).map_err(|e| e.to_string())
+        });
+        let response = match proved {
+            Ok(proof) => ProveResponse::Proof(proof),
+            Err(e) => ProveResponse::Error(e),
+        };
+        output.write_all(&encode_frame(&response)?)?;
+        output.flush()?;
+    }
+    Ok(())
+}
+
+fn encode_frame<T: Serialize>(value: &T) -> Result<Vec<u8>, ProverError> {
+    let body = postcard::to_allocvec(value)?;
+    let len = u32::try_from(body.len())
+        .ok()
+        .filter(|len| *len <= PROVER_CHILD_MAX_FRAME_BYTES)
+        .ok_or_else(|| frame_too_large(body.len()))?;
+    let mut frame = Vec::with_capacity(4 + body.len());
+    frame.extend_from_slice(&len.to_le_bytes());
+    frame.extend_from_slice(&body);
+    Ok(frame)
+}
+
+fn frame_len(header: [u8; 4]) -> Result<usize, ProverError> {
+    let len = u32::from_le_bytes(header);
+    if len > PROVER_CHILD_MAX_FRAME_BYTES {
+        return Err(frame_too_large(len as usize));
+    }
+    Ok(len as usize)
+}
+
+fn frame_too_large(len: usize) -> ProverError {
+    ProverError::Io(std::io::Error::new(
+        ErrorKind::InvalidData,
+        format!("prover frame of {} bytes exceeds the size limit", len),
+    ))
+}
+
+/// Read one frame, or `None` if the stream ended cleanly before it
+fn read_frame<T: DeserializeOwned>(input: &mut impl Read) -> Result<Option<T>, ProverError> {
+    let mut header = [0u8; 4];
+    match input.read_exact(&mut header) {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+    let mut body = vec![0u8; frame_len(header)?];
+    input.read_exact(&mut body)?;
+    Ok(Some(postcard::from_bytes(&body)?))
+}
+
+async fn read_frame_async<T: DeserializeOwned>(
+    input: &mut (impl AsyncReadExt + Unpin),
+) -> Result<T, ProverError> {
+    let mut header = [0u8; 4];
+    input.read_exact(&mut header).await?;
+    let mut body = vec![0u8; frame_len(header)?];
+    input.read_exact(&mut body).await?;
+    Ok(postcard::from_bytes(&body)?)
+}
+
+/// When a prover child is replaced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecyclePolicy {
+    pub max_proofs: u32,
+    /// Resident memory growth allowed over the child's size after its first proof
+    pub max_rss_growth: u64,
+}
+
+impl Default for RecyclePolicy {
+    fn default() -> Self {
+        Self {
+            max_proofs: PROVER_CHILD_MAX_PROOFS,
+            max_rss_growth: PROVER_CHILD_MAX_RSS_GROWTH,
+        }
+    }
+}
+
+impl RecyclePolicy {
+    pub fn should_recycle(&self, proofs: u32, baseline_rss: Option<u64>, rss: Option<u64>) -> bool {
+        let grown = match (baseline_rss, rss) {
+            (Some(baseline), Some(rss)) => rss.saturating_sub(baseline) > self.max_rss_growth,
+            _ => false,
+        };
+        proofs >= self.max_proofs || grown
+    }
+}
+
+/// Parent side: a prover child that is started on first use and replaced as needed
+pub struct ProverChild {
+    events: Option<(EventSender, usize)>,
+    policy: RecyclePolicy,
+    running: Option<RunningChild>,
+    sysinfo: System,
+}
+
+struct RunningChild {
+    process: Child,
+    stdin: ChildStdin,
+    stdout: ChildStdout,
+    stderr_tail: Arc<StderrTail>,
+    stderr_reader: JoinHandle<()>,
+    proofs: u32,
+    baseline_rss: Option<u64>,
+}
+
+impl ProverChild {
+    /// Prover output is forwarded as events from the given prover thread when `events` is
+    /// given, and otherwise written to the log file.
+    pub fn new(events: Option<(EventSender, usize)>) -> Self {
+        Self {
+            events,
+            policy: RecyclePolicy::default(),
+            running: None,
+            sysinfo: System::new(),
+        }
+    }
+
+    /// Prove the given inputs, starting a child if none is running.
+    ///
+    /// The child is held by this call while it proves, so cancelling the call drops the
+    /// child, which kills it; the next call starts a fresh one.
+    pub async fn prove(&mut self, inputs: &(u32, u32, u32)) -> Result<Proof, ProverError> {
+        let mut running = match self.running.take() {
+            Some(running) => running,
+            None => self.spawn()?,
+        };
+        running.stderr_tail.clear();
+
+        let response = match running.request(inputs).await {
+            Ok(response) => response,
+            Err(e) => return Err(running.failure(e).await),
+        };
+
+        let rss = self.child_rss(&running);
+        running.proofs += 1;
+        if running.proofs == 1 {
+            // Proving memory is only fully allocated once; growth is measured from here
+            running.baseline_rss = rss;
+        }
+        let result = match response {
+            ProveResponse::Proof(proof) => Ok(proof),
+            ProveResponse::Error(message) => Err(ProverError::Subprocess {
+                message: format!("Error while proving within subprocess: {}", message),
+                stderr_tail: running.stderr_tail.lines(),
+            }),
+        };
+        if self
+            .policy
+            .should_recycle(running.proofs, running.baseline_rss, rss)
+        {
+            running.exit().await;
+        } else {
+            self.running = Some(running);
+        }
+        result
+    }
+
+    fn spawn(&self) -> Result<RunningChild, ProverError> {
+        let mut process = tokio::process::Command::new(env::current_exe()?)
+            .arg("prove-fib-server")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+        let stdin = process.stdin.take().expect("stdin is piped");
+        let stdout = process.stdout.take().expect("stdout is piped");
+        let stderr = process.stderr.take().expect("stderr is piped");
+
+        let stderr_tail = Arc::new(StderrTail::default());
+        let tail = stderr_tail.clone();
+        let events = self.events.clone();
+        let stderr_reader = tokio::spawn(async move {
+            let events = events.as_ref().map(|(sender, thread)| (sender, *thread));
+            read_stderr(stderr, events, &tail).await;
+        });
+
+        Ok(RunningChild {
+            process,
+            stdin,
+            stdout,
+            stderr_tail,
+            stderr_reader,
+            proofs: 0,
+            baseline_rss: None,
+        })
+    }
+
+    /// Resident memory of a running child, in bytes
+    fn child_rss(&mut self, running: &RunningChild) -> Option<u64> {
+        let pid = Pid::from_u32(running.process.id()?);
+        self.sysinfo.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid]),
+            true,
+            ProcessRefreshKind::nothing().with_memory(),
+        );
+        self.sysinfo.process(pid).map(|process| process.memory())
+    }
+}
+
+/// The prover children a worker proves a task's inputs with, one per input proven at once
+pub struct ProverPool {
+    events: Option<(EventSender, usize)>,
+    slots: usize,
+    idle: Vec<ProverChild>,
+}
+
+impl ProverPool {
+    pub fn new(slots: usize, events: Option<(EventSender, usize)>) -> Self {
+        Self {
+            events,
+            slots: slots.max(1),
+            idle: Vec::new(),
+        }
+    }
+
+    /// A pool of at most `max_slots`, fewer if the memory currently available to provers
+    /// does not fit that many
+    pub fn for_available_memory(max_slots: usize, events: Option<(EventSender, usize)>) -> Self {
+        Self::new(
+            slots_for_memory(system::available_memory_bytes(), system::num_cores()).min(max_slots),
+            events,
+        )
+    }
+
+    /// Number of inputs that may be proven at once
+    pub fn slots(&self) -> usize {
+        self.slots
+    }
+
+    /// Take an idle child, or a new one if all are in use or were cancelled
+    pub fn checkout(&mut self) -> ProverChild {
+        self.idle
+            .pop()
+            .unwrap_or_else(|| ProverChild::new(self.events.clone()))
+    }
+
+    pub fn checkin(&mut self, child: ProverChild) {
+        if self.idle.len() < self.slots {
+            self.idle.push(child);
+        }
+    }
+}
+
+/// Number of prover slots that fit in `available_bytes` of memory, at most one per core
+pub fn slots_for_memory(available_bytes: u64, cores: usize) -> usize {
+    let budget = available_bytes as f64 * PROVER_SLOT_MEMORY_HEADROOM;
+    let by_memory = (budget / PROJECTED_MEMORY_REQUIREMENT as f64) as usize;
+    by_memory.min(cores).clamp(1, PROVER_MAX_SLOTS)
+}
+
+impl RunningChild {
+    async fn request(&mut self, inputs: &(u32, u32, u32)) -> Result<ProveResponse, ProverError> {
+        let frame = encode_frame(&ProveRequest { inputs: *inputs })?;
+        self.stdin.write_all(&frame).await?;
+        self.stdin.flush().await?;
+        read_frame_async(&mut self.stdout).await
+    }
+
+    /// Explain a failed request. If the pipe closed, the child has died and its exit status
+    /// says why; any other error means the child is unusable and is killed.
+    async fn failure(mut self, error: ProverError) -> ProverError {
+        let died = matches!(
+            &error,
+            ProverError::Io(e) if matches!(e.kind(), ErrorKind::UnexpectedEof | ErrorKind::BrokenPipe)
+        );
+        if !died {
+            let _ = self.process.kill().await;
+            return ProverError::Subprocess {
+                message: format!("Prover child sent an invalid response: {}", error),
+                stderr_tail: self.stderr_tail.lines(),
+            };
+        }
+        drop(self.stdin);
+        let status = match self.process.wait().await {
+            Ok(status) => status,
+            Err(e) => return e.into(),
+        };
+        // The child's stderr closes as it exits, so the tail is complete once the reader ends
+        let _ = self.stderr_reader.await;
+        subprocess_failure(&SubprocessOutput {
+            status,
+            stdout: Vec::new(),
+            stderr_tail: self.stderr_tail.lines(),
+        })
+    }
+
+    async fn exit(mut self) {
+        drop(self.stdin);
+        let timeout = Duration::from_secs(PROVER_CHILD_EXIT_TIMEOUT_SECS);
+        if tokio::time::timeout(timeout, self.process.wait())
+            .await
+            .is_err()
+        {
+            let _ = self.process.kill().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_frames_round_trip() {
+        let request = ProveRequest {
+            inputs: (1_000, 1, 1),
+        };
+        let mut stream = encode_frame(&request).unwrap();
+        stream.extend(encode_frame(&request).unwrap());
+
+        let mut reader = stream.as_slice();
+        assert_eq!(read_frame(&mut reader).unwrap(), Some(request.clone()));
+        assert_eq!(
+            read_frame_async::<ProveRequest>(&mut reader).await.unwrap(),
+            request
+        );
+        // A cleanly closed stream ends the child's loop
+        assert_eq!(read_frame::<ProveRequest>(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn test_oversized_frame_is_rejected() {
+        let header = (PROVER_CHILD_MAX_FRAME_BYTES + 1).to_le_bytes();
+        assert!(matches!(
+            read_frame::<ProveRequest>(&mut header.as_slice()),
+            Err(ProverError::Io(e)) if e.kind() == ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_recycle_policy() {
+        let policy = RecyclePolicy {
+            max_proofs: 3,
+            max_rss_growth: 100,
+        };
+        assert!(!policy.should_recycle(2, Some(1_000), Some(1_100)));
+        assert!(policy.should_recycle(2, Some(1_000), Some(1_101)));
+        assert!(policy.should_recycle(3, Some(1_000), Some(1_000)));
+        // Memory that cannot be measured never triggers a recycle on its own
+        assert!(!policy.should_recycle(1, None, Some(5_000)));
+    }
+
+    #[test]
+    fn test_slots_for_memory() {
+        let gb = 1024 * 1024 * 1024;
+        assert_eq!(slots_for_memory(2 * gb, 8), 1);
+        assert_eq!(slots_for_memory(11 * gb, 8), 2);
+        assert_eq!(slots_for_memory(11 * gb, 1), 1);
+        assert_eq!(slots_for_memory(256 * gb, 64), PROVER_MAX_SLOTS);
+    }
+
+    #[test]
+    fn test_pool_never_exceeds_max_threads() {
+        assert_eq!(ProverPool::for_available_memory(1, None).slots(), 1);
+        assert!(ProverPool::for_available_memory(2, None).slots() <= 2);
+    }
+}
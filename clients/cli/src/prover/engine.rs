@@ -2,18 +2,33 @@
 
 use crate::prover::verifier;
 
+use super::child::ProverChild;
 use super::types::ProverError;
 use crate::analytics::track_likely_oom_error;
+use crate::consts::cli_consts::{
+    SUBPROCESS_INTERNAL_ERROR_CODE, SUBPROCESS_STDERR_TAIL_LINES, SUBPROCESS_SUSPECTED_OOM_CODE,
+};
 use crate::environment::Environment;
+use crate::events::EventType;
+use crate::logging::LogLevel;
 use crate::task::Task;
+use crate::workers::core::EventSender;
 use nexus_sdk::{
     Local, Prover,
     stwo::seq::{Proof, Stwo},
 };
 use postcard::from_bytes;
 use serde_json;
+use std::collections::VecDeque;
 use std::env;
-use std::process::Stdio;
+use std::process::{ExitStatus, Stdio};
+use std::sync::{Mutex, OnceLock};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::process::ChildStderr;
+use tokio::task::JoinHandle;
+
+/// The fibonacci guest program
+pub const FIB_INPUT_INITIAL_ELF: &[u8] = include_bytes!("../../assets/fib_input_initial");
 
 /// Core proving engine for ZK proof generation
 pub struct ProvingEngine;
@@ -21,8 +36,7 @@ pub struct ProvingEngine;
 impl ProvingEngine {
     /// Create a Stwo prover instance for the fibonacci program
     pub fn create_fib_prover() -> Result<Stwo<Local>, ProverError> {
-        let elf_bytes = include_bytes!("../../assets/fib_input_initial");
-        Stwo::<Local>::new_from_bytes(elf_bytes).map_err(|e| {
+        Stwo::<Local>::new_from_bytes(FIB_INPUT_INITIAL_ELF).map_err(|e| {
             ProverError::Stwo(format!(
                 "Failed to load fib_input_initial guest program: {}",
                 e
@@ -32,7 +46,25 @@ impl ProvingEngine {
 
     /// Subprocess entrypoint: generate proof without verification
     pub fn prove_fib_subprocess(inputs: &(u32, u32, u32)) -> Result<Proof, ProverError> {
-        let prover = Self::create_fib_prover()?;
+        Self::prove_fib_with(Self::create_fib_prover()?, inputs)
+    }
+
+    /// Generate a proof without verification using a prover built from the already loaded
+    /// guest program of `program`, which is left untouched for the next input
+    pub fn prove_fib_reusing(
+        program: &Stwo<Local>,
+        inputs: &(u32, u32, u32),
+    ) -> Result<Proof, ProverError> {
+        let prover = Stwo::<Local>::new(&program.elf).map_err(|e| {
+            ProverError::Stwo(format!(
+                "Failed to load fib_input_initial guest program: {}",
+                e
+            ))
+        })?;
+        Self::prove_fib_with(prover, inputs)
+    }
+
+    fn prove_fib_with(prover: Stwo<Local>, inputs: &(u32, u32, u32)) -> Result<Proof, ProverError> {
         let (view, proof) = prover
             .prove_with_input::<(), (u32, u32, u32)>(&(), inputs)
             .map_err(|e| {
@@ -47,57 +79,274 @@ impl ProvingEngine {
         Ok(proof)
     }
 
-    /// Generate proof for given inputs using the fibonacci program in a subprocess
-    pub async fn prove_and_validate(
+    /// Run the hidden `prove-fib-subprocess` command for the given inputs and collect its output.
+    ///
+    /// This starts a new process for a single proof; workers keep a [`ProverChild`] running
+    /// instead. The subprocess's stderr is read line by line rather than inherited, so it
+    /// cannot draw over the dashboard.
+    pub async fn run_prover_subprocess(
         inputs: &(u32, u32, u32),
-        task: &Task,
-        environment: &Environment,
-        client_id: &str,
-    ) -> Result<Proof, ProverError> {
+        events: Option<(&EventSender, usize)>,
+    ) -> Result<SubprocessOutput, ProverError> {
         // Spawn a subprocess for proof generation to isolate memory usage
         let exe_path = env::current_exe()?;
-        let mut cmd = tokio::process::Command::new(exe_path);
-        cmd.arg("prove-fib-subprocess")
+        let mut child = tokio::process::Command::new(exe_path)
+            .arg("prove-fib-subprocess")
             .arg("--inputs")
             .arg(serde_json::to_string(inputs)?)
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
-            .stderr(Stdio::inherit());
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+        let mut stdout = child.stdout.take().expect("stdout is piped");
+        let stderr = child.stderr.take().expect("stderr is piped");
 
-        let output = cmd.output().await?;
+        let read_stdout = async {
+            let mut buf = Vec::new();
+            stdout.read_to_end(&mut buf).await.map(|_| buf)
+        };
+        let tail = StderrTail::default();
 
-        if !output.status.success() {
-            if let Some(code) = output.status.code() {
-                if code == crate::consts::cli_consts::SUBPROCESS_SUSPECTED_OOM_CODE {
-                    // 128 + 9 = 137 means external sigkill, so likely killed by kernel due to OOM; track analytics event
-                    tokio::spawn(track_likely_oom_error(
-                        task.clone(),
-                        environment.clone(),
-                        client_id.to_string(),
-                    ));
-                }
-
-                if code == crate::consts::cli_consts::SUBPROCESS_INTERNAL_ERROR_CODE {
-                    // error happened inside the subprocess, and so we know that it may be useful information to the user
-                    return Err(ProverError::Subprocess(format!(
-                        "Error while proving within subprocess, captured error: [{}]",
-                        &String::from_utf8_lossy(&output.stderr)
-                    )));
-                }
-            }
+        let (stdout, _) = tokio::join!(read_stdout, read_stderr(stderr, events, &tail));
+        let status = child.wait().await?;
+        Ok(SubprocessOutput {
+            status,
+            stdout: stdout?,
+            stderr_tail: tail.lines(),
+        })
+    }
 
-            return Err(ProverError::Subprocess(format!(
-                "Prover subprocess failed with status: {}",
-                output.status
-            )));
+    /// Extract the proof from a finished prover subprocess, or the error it reported
+    pub fn proof_from_subprocess_output(output: &SubprocessOutput) -> Result<Proof, ProverError> {
+        if !output.status.success() {
+            return Err(subprocess_failure(output));
         }
 
         // Deserialize proof from subprocess stdout
-        let proof: Proof = from_bytes(&output.stdout)?;
+        Ok(from_bytes(&output.stdout)?)
+    }
 
-        // Verify proof in main process
-        let verify_prover = Self::create_fib_prover()?;
-        verifier::ProofVerifier::verify_proof(&proof, inputs, &verify_prover)?;
+    /// The fibonacci guest program, loaded once and shared by every verification
+    pub fn fib_verifier() -> Result<&'static Stwo<Local>, ProverError> {
+        static FIB_VERIFIER: OnceLock<Stwo<Local>> = OnceLock::new();
+        if let Some(prover) = FIB_VERIFIER.get() {
+            return Ok(prover);
+        }
+        let prover = Self::create_fib_prover()?;
+        Ok(FIB_VERIFIER.get_or_init(|| prover))
+    }
 
-        Ok(proof)
+    /// Verify a proof on the blocking pool, handing it back once it has passed
+    pub fn spawn_verification(
+        proof: Proof,
+        inputs: (u32, u32, u32),
+    ) -> JoinHandle<Result<Proof, ProverError>> {
+        tokio::task::spawn_blocking(move || {
+            verifier::ProofVerifier::verify_proof(&proof, &inputs, Self::fib_verifier()?)?;
+            Ok(proof)
+        })
+    }
+
+    /// Generate proof for given inputs using the fibonacci program in the prover child
+    pub async fn prove(
+        prover: &mut ProverChild,
+        inputs: &(u32, u32, u32),
+        task: &Task,
+        environment: &Environment,
+        client_id: &str,
+    ) -> Result<Proof, ProverError> {
+        let result = prover.prove(inputs).await;
+        if let Err(ProverError::Killed { .. }) = &result {
+            // Killed from outside, so likely by the kernel due to OOM; track analytics event
+            tokio::spawn(track_likely_oom_error(
+                task.clone(),
+                environment.clone(),
+                client_id.to_string(),
+            ));
+        }
+        result
+    }
+}
+
+/// Output of a finished prover subprocess
+#[derive(Debug)]
+pub struct SubprocessOutput {
+    pub status: ExitStatus,
+    pub stdout: Vec<u8>,
+    /// The last lines written to stderr
+    pub stderr_tail: Vec<String>,
+}
+
+/// Signal numbers shared by Linux and macOS
+#[cfg(unix)]
+mod signal {
+    pub const SIGABRT: i32 = 6;
+    pub const SIGKILL: i32 = 9;
+    pub const SIGSEGV: i32 = 11;
+}
+
+/// The last lines a prover subprocess wrote to stderr
+#[derive(Debug, Default)]
+pub struct StderrTail(Mutex<VecDeque<String>>);
+
+impl StderrTail {
+    fn push(&self, line: String) {
+        let mut tail = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if tail.len() == SUBPROCESS_STDERR_TAIL_LINES {
+            tail.pop_front();
+        }
+        tail.push_back(line);
+    }
+
+    pub fn clear(&self) {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        Vec::from(self.0.lock().unwrap_or_else(|e| e.into_inner()).clone())
+    }
+}
+
+/// Read a prover subprocess's stderr line by line until it is closed, keeping the tail.
+///
+/// Each line is forwarded as a prover event when `events` is given, and otherwise written
+/// to the log file.
+pub async fn read_stderr(
+    stderr: ChildStderr,
+    events: Option<(&EventSender, usize)>,
+    tail: &StderrTail,
+) {
+    let mut lines = BufReader::new(stderr).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let level = stderr_line_level(&line);
+        match events {
+            Some((sender, thread_id)) => {
+                sender
+                    .send_prover_event(
+                        thread_id,
+                        format!("Prover output: {}", line),
+                        EventType::Refresh,
+                        level,
+                    )
+                    .await
+            }
+            None => log::log!(target: "nexus_network::prover", level.into(), "{}", line),
+        }
+        tail.push(line);
+    }
+}
+
+/// Decode why a prover subprocess failed, distinguishing crash signals from error exits
+pub fn subprocess_failure(output: &SubprocessOutput) -> ProverError {
+    let stderr_tail = output.stderr_tail.clone();
+
+    #[cfg(unix)]
+    if let Some(signal) = std::os::unix::process::ExitStatusExt::signal(&output.status) {
+        return match signal {
+            signal::SIGKILL => ProverError::Killed { stderr_tail },
+            signal::SIGSEGV => ProverError::Segfault { stderr_tail },
+            signal::SIGABRT => ProverError::Aborted { stderr_tail },
+            signal => ProverError::Signal {
+                signal,
+                stderr_tail,
+            },
+        };
+    }
+
+    match output.status.code() {
+        // 128 + 9: SIGKILL reported as an exit code, e.g. by a wrapper shell
+        Some(SUBPROCESS_SUSPECTED_OOM_CODE) => ProverError::Killed { stderr_tail },
+        Some(SUBPROCESS_INTERNAL_ERROR_CODE) => ProverError::Subprocess {
+            // The error happened inside the subprocess, so its last words are useful to the user
+            message: format!(
+                "Error while proving within subprocess: {}",
+                stderr_tail.last().map_or("no output", String::as_str)
+            ),
+            stderr_tail,
+        },
+        _ => ProverError::Subprocess {
+            message: format!("Prover subprocess failed with status: {}", output.status),
+            stderr_tail,
+        },
+    }
+}
+
+/// Guess the level of a stderr line from the level names and panic messages it contains
+fn stderr_line_level(line: &str) -> LogLevel {
+    let upper = line.to_ascii_uppercase();
+    if line.contains("panicked at") || upper.contains("ERROR") {
+        LogLevel::Error
+    } else if upper.contains("WARN") {
+        LogLevel::Warn
+    } else if upper.contains("DEBUG") {
+        LogLevel::Debug
+    } else if upper.contains("TRACE") {
+        LogLevel::Trace
+    } else {
+        LogLevel::Info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stderr_line_level() {
+        assert_eq!(
+            stderr_line_level("thread 'main' panicked at src/main.rs:10:5"),
+            LogLevel::Error
+        );
+        assert_eq!(
+            stderr_line_level("2025-01-01T00:00:00Z WARN stwo: slow"),
+            LogLevel::Warn
+        );
+        assert_eq!(stderr_line_level("[debug] loading guest"), LogLevel::Debug);
+        assert_eq!(stderr_line_level("loading guest"), LogLevel::Info);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_subprocess_failure_decodes_signals_and_exit_codes() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let output = |raw: i32| SubprocessOutput {
+            status: ExitStatus::from_raw(raw),
+            stdout: Vec::new(),
+            stderr_tail: vec!["first".to_string(), "last".to_string()],
+        };
+
+        // Raw wait statuses: a signal number in the low bits, or an exit code shifted left by 8
+        assert!(matches!(
+            subprocess_failure(&output(9)),
+            ProverError::Killed { .. }
+        ));
+        assert!(matches!(
+            subprocess_failure(&output(11)),
+            ProverError::Segfault { .. }
+        ));
+        assert!(matches!(
+            subprocess_failure(&output(6)),
+            ProverError::Aborted { .. }
+        ));
+        assert!(matches!(
+            subprocess_failure(&output(15)),
+            ProverError::Signal { signal: 15, .. }
+        ));
+        assert!(matches!(
+            subprocess_failure(&output(137 << 8)),
+            ProverError::Killed { .. }
+        ));
+
+        let error = subprocess_failure(&output(3 << 8));
+        assert_eq!(
+            error.to_string(),
+            "Subprocess error: Error while proving within subprocess: last"
+        );
+        assert_eq!(error.stderr_tail(), ["first", "last"]);
     }
 }
@@ -1,16 +1,32 @@
 //! High-level proving interface
 
+use super::child::ProverPool;
 use super::pipeline::ProvingPipeline;
 use super::types::ProverError;
 use crate::environment::Environment;
 use crate::task::Task;
+use crate::thermal::ThermalMonitor;
+use crate::workers::core::EventSender;
 use nexus_sdk::stwo::seq::Proof;
 
 /// Proves a program with authenticated task inputs
 pub async fn authenticated_proving(
+    provers: &mut ProverPool,
+    thermal: &mut ThermalMonitor,
     task: &Task,
     environment: &Environment,
     client_id: &str,
+    event_sender: &EventSender,
+    verify: bool,
 ) -> Result<(Vec<Proof>, String, Vec<String>), ProverError> {
-    ProvingPipeline::prove_authenticated(task, environment, client_id).await
+    ProvingPipeline::prove_authenticated(
+        provers,
+        thermal,
+        task,
+        environment,
+        client_id,
+        event_sender,
+        verify,
+    )
+    .await
 }
@@ -1,3 +1,4 @@
+pub mod child;
 pub mod engine;
 pub mod handlers;
 pub mod input;
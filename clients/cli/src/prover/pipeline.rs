@@ -1,13 +1,27 @@
 //! Proving pipeline that orchestrates the full proving process
 
+use super::child::ProverPool;
 use super::engine::ProvingEngine;
 use super::input::InputParser;
 use super::types::ProverError;
 use crate::analytics::track_verification_failed;
+use crate::consts::cli_consts::PROGRESS_ROLLING_WINDOW;
 use crate::environment::Environment;
+use crate::events::{Event, ProvingProgress};
 use crate::task::Task;
+use crate::thermal::ThermalMonitor;
+use crate::workers::core::EventSender;
 use nexus_sdk::stwo::seq::Proof;
 use sha3::{Digest, Keccak256};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use tokio::task::{JoinHandle, JoinSet};
+
+/// A proven input, possibly still being verified
+enum ProvenInput {
+    Verifying(JoinHandle<Result<Proof, ProverError>>),
+    Ready(Proof),
+}
 
 /// Orchestrates the complete proving pipeline
 pub struct ProvingPipeline;
@@ -15,12 +29,27 @@ pub struct ProvingPipeline;
 impl ProvingPipeline {
     /// Execute authenticated proving for a task
     pub async fn prove_authenticated(
+        provers: &mut ProverPool,
+        thermal: &mut ThermalMonitor,
         task: &Task,
         environment: &Environment,
         client_id: &str,
+        event_sender: &EventSender,
+        verify: bool,
     ) -> Result<(Vec<Proof>, String, Vec<String>), ProverError> {
         match task.program_id.as_str() {
-            "fib_input_initial" => Self::prove_fib_task(task, environment, client_id).await,
+            "fib_input_initial" => {
+                Self::prove_fib_task(
+                    provers,
+                    thermal,
+                    task,
+                    environment,
+                    client_id,
+                    event_sender,
+                    verify,
+                )
+                .await
+            }
             _ => Err(ProverError::MalformedTask(format!(
                 "Unsupported program ID: {}",
                 task.program_id
@@ -28,11 +57,20 @@ impl ProvingPipeline {
         }
     }
 
-    /// Process fibonacci proving task with multiple inputs
+    /// Process fibonacci proving task with multiple inputs, reporting progress after each input.
+    ///
+    /// Inputs are proven concurrently, one per slot of the prover pool, and the first failure
+    /// cancels the rest. Fewer inputs are started while the machine runs hot, and none while
+    /// it is over its limits. Each proof is verified on the blocking pool while other inputs
+    /// are proven; the task only completes once every verification has passed.
     async fn prove_fib_task(
+        provers: &mut ProverPool,
+        thermal: &mut ThermalMonitor,
         task: &Task,
         environment: &Environment,
         client_id: &str,
+        event_sender: &EventSender,
+        verify: bool,
     ) -> Result<(Vec<Proof>, String, Vec<String>), ProverError> {
         let all_inputs = task.all_inputs();
 
@@ -42,44 +80,140 @@ impl ProvingPipeline {
             ));
         }
 
-        let mut proof_hashes = Vec::new();
-        let mut all_proofs: Vec<Proof> = Vec::new();
+        // Step 1: Parse and validate every input before proving any
+        let all_inputs = all_inputs
+            .iter()
+            .map(|input_data| InputParser::parse_triple_input(input_data))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let total = all_inputs.len();
+        let parallelism = provers.slots().min(total);
+        let mut proof_hashes: Vec<Option<String>> = vec![None; total];
+        let mut proven: Vec<Option<ProvenInput>> = (0..total).map(|_| None).collect();
+        let task_start = Instant::now();
+        let mut recent_durations: VecDeque<Duration> =
+            VecDeque::with_capacity(PROGRESS_ROLLING_WINDOW);
 
-        for (input_index, input_data) in all_inputs.iter().enumerate() {
-            // Step 1: Parse and validate input
-            let inputs = InputParser::parse_triple_input(input_data)?;
+        // Dropping the set on an early return aborts the remaining inputs, and with them
+        // their prover children
+        let mut in_flight = JoinSet::new();
+        let mut next_input = 0;
+        let mut completed = 0;
+        while completed < total {
+            let allowed = thermal.allowed_parallelism(parallelism, event_sender).await;
+            if allowed == 0 && in_flight.is_empty() {
+                tokio::time::sleep(thermal.poll_interval()).await;
+                continue;
+            }
+            while next_input < total && in_flight.len() < allowed {
+                let mut prover = provers.checkout();
+                let (input_index, inputs) = (next_input, all_inputs[next_input]);
+                let (task, environment, client_id) =
+                    (task.clone(), environment.clone(), client_id.to_string());
+                in_flight.spawn(async move {
+                    let input_start = Instant::now();
+                    // Step 2: Generate proof
+                    let result =
+                        ProvingEngine::prove(&mut prover, &inputs, &task, &environment, &client_id)
+                            .await;
+                    (input_index, prover, input_start.elapsed(), result)
+                });
+                next_input += 1;
+            }
 
-            // Step 2: Generate and verify proof
-            let proof = ProvingEngine::prove_and_validate(&inputs, task, environment, client_id)
+            let (input_index, prover, input_duration, result) = in_flight
+                .join_next()
                 .await
-                .map_err(|e| {
-                    match e {
-                        ProverError::Stwo(_) | ProverError::GuestProgram(_) => {
-                            // Track verification failure
-                            let error_msg = format!("Input {}: {}", input_index, e);
-                            tokio::spawn(track_verification_failed(
-                                task.clone(),
-                                error_msg.clone(),
-                                environment.clone(),
-                                client_id.to_string(),
-                            ));
-                            e
-                        }
-                        _ => e,
-                    }
-                })?;
-
-            // Step 3: Generate proof hash
-            let proof_hash = Self::generate_proof_hash(&proof);
-            proof_hashes.push(proof_hash);
+                .expect("inputs remain in flight")
+                .map_err(|e| ProverError::Stwo(format!("Proving did not complete: {}", e)))?;
+            provers.checkin(prover);
+            let proof = result?;
+
+            // Step 3: Generate proof hash, then verify in the background
+            proof_hashes[input_index] = Some(Self::generate_proof_hash(&proof));
+            proven[input_index] = Some(if verify {
+                ProvenInput::Verifying(ProvingEngine::spawn_verification(
+                    proof,
+                    all_inputs[input_index],
+                ))
+            } else {
+                ProvenInput::Ready(proof)
+            });
+            completed += 1;
+
+            // Step 4: Report progress
+            if recent_durations.len() == PROGRESS_ROLLING_WINDOW {
+                recent_durations.pop_front();
+            }
+            recent_durations.push_back(input_duration);
+            let average_input_duration =
+                recent_durations.iter().sum::<Duration>() / recent_durations.len() as u32;
+
+            event_sender
+                .send_event(Event::proving_progress(
+                    0, // Inputs share one progress line
+                    ProvingProgress {
+                        task_id: task.task_id.clone(),
+                        completed,
+                        total,
+                        elapsed: task_start.elapsed(),
+                        last_input_duration: input_duration,
+                        average_input_duration,
+                        parallelism,
+                    },
+                ))
+                .await;
+        }
+
+        // Proofs and hashes keep the order of the inputs
+        let mut all_proofs = Vec::with_capacity(total);
+        for (input_index, input) in proven.into_iter().enumerate() {
+            let proof = match input.expect("every input was proven") {
+                ProvenInput::Verifying(verification) => {
+                    Self::finish_verification(
+                        (input_index, verification),
+                        task,
+                        environment,
+                        client_id,
+                    )
+                    .await?
+                }
+                ProvenInput::Ready(proof) => proof,
+            };
             all_proofs.push(proof);
         }
+        let proof_hashes: Vec<String> = proof_hashes.into_iter().flatten().collect();
 
         let final_proof_hash = Self::combine_proof_hashes(task, &proof_hashes);
 
         Ok((all_proofs, final_proof_hash, proof_hashes))
     }
 
+    /// Wait for a background verification, tracking its failure
+    async fn finish_verification(
+        (input_index, verification): (usize, JoinHandle<Result<Proof, ProverError>>),
+        task: &Task,
+        environment: &Environment,
+        client_id: &str,
+    ) -> Result<Proof, ProverError> {
+        let result = verification.await.unwrap_or_else(|e| {
+            Err(ProverError::Stwo(format!(
+                "Proof verification did not complete: {}",
+                e
+            )))
+        });
+        if let Err(e @ (ProverError::Stwo(_) | ProverError::GuestProgram(_))) = &result {
+            // Track verification failure
+            tokio::spawn(track_verification_failed(
+                task.clone(),
+                format!("Input {}: {}", input_index, e),
+                environment.clone(),
+                client_id.to_string(),
+            ));
+        }
+        result
+    }
+
     /// Generate hash for a proof
     fn generate_proof_hash(proof: &Proof) -> String {
         let proof_bytes = postcard::to_allocvec(proof).expect("Failed to serialize proof");
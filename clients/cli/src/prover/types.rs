@@ -20,13 +20,46 @@ pub enum ProverError {
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
 
-    #[error("Subprocess error: {0}")]
-    Subprocess(String),
+    #[error("Subprocess error: {message}")]
+    Subprocess {
+        message: String,
+        stderr_tail: Vec<String>,
+    },
+
+    /// The subprocess received SIGKILL, almost always from the out-of-memory killer
+    #[error("Prover subprocess was killed (SIGKILL), most likely because it ran out of memory")]
+    Killed { stderr_tail: Vec<String> },
+
+    #[error("Prover subprocess crashed with a segmentation fault (SIGSEGV)")]
+    Segfault { stderr_tail: Vec<String> },
+
+    #[error("Prover subprocess aborted (SIGABRT)")]
+    Aborted { stderr_tail: Vec<String> },
+
+    #[error("Prover subprocess was terminated by signal {signal}")]
+    Signal {
+        signal: i32,
+        stderr_tail: Vec<String>,
+    },
 
     #[error("Serde JSON error: {0}")]
     SerdeJson(#[from] serde_json::Error),
 }
 
+impl ProverError {
+    /// The last lines the prover subprocess wrote to stderr, if it failed
+    pub fn stderr_tail(&self) -> &[String] {
+        match self {
+            ProverError::Subprocess { stderr_tail, .. }
+            | ProverError::Killed { stderr_tail }
+            | ProverError::Segfault { stderr_tail }
+            | ProverError::Aborted { stderr_tail }
+            | ProverError::Signal { stderr_tail, .. } => stderr_tail,
+            _ => &[],
+        }
+    }
+}
+
 /// Result of a proof generation, including combined hash for multiple inputs
 pub struct ProverResult {
     pub proofs: Vec<Proof>,
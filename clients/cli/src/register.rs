@@ -3,7 +3,9 @@
 use crate::cli_messages::{print_error, print_info, print_success};
 use crate::config::Config;
 use crate::keys;
+use crate::network::{Operation, RetryPolicy, retry};
 use crate::orchestrator::Orchestrator;
+use crate::orchestrator::error::ServerError;
 use std::path::Path;
 
 /// Registers a user with the orchestrator.
@@ -17,21 +19,22 @@ pub async fn register_user(
     config_path: &Path,
     orchestrator: Box<dyn Orchestrator>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    // Check if the wallet address is valid.
-    if !keys::is_valid_eth_address(wallet_address) {
-        print_error(
-            "Invalid Ethereum wallet address",
-            Some(&format!(
-                "It should be a 42-character hex string starting with '0x', got: {}",
-                wallet_address
-            )),
-        );
-        let err_msg = format!(
-            "Invalid Ethereum wallet address: {}. It should be a 42-character hex string starting with '0x'.",
-            wallet_address
-        );
-        return Err(Box::from(err_msg));
-    }
+    // Check that the wallet address is valid, and use its checksummed form from here on so the
+    // same wallet is always registered and saved the same way.
+    let wallet_address = match keys::parse_eth_address(wallet_address) {
+        Ok(address) => address,
+        Err(e) => {
+            print_error(
+                "Invalid Ethereum wallet address",
+                Some(&format!("{}: {}", wallet_address, e)),
+            );
+            return Err(Box::from(format!(
+                "Invalid Ethereum wallet address {}: {}",
+                wallet_address, e
+            )));
+        }
+    };
+    let wallet_address = wallet_address.as_str();
 
     // Check if the config file exists and contains this wallet address and a user ID.
     if config_path.exists() {
@@ -58,21 +61,21 @@ pub async fn register_user(
     }
 
     // Check if the wallet address is already registered with the orchestrator.
-    if let Ok(user_id) = orchestrator.get_user(wallet_address).await {
+    let existing_user = match orchestrator.get_user(wallet_address).await {
+        Ok(user_id) => Some(user_id),
+        Err(e) if e.server_error().is_some_and(ServerError::is_not_found) => None,
+        Err(e) => {
+            print_error("Failed to look up wallet address", Some(&e.to_string()));
+            return Err(e.into());
+        }
+    };
+    if let Some(user_id) = existing_user {
         print_info(
             "Wallet address is already registered",
             &format!("User ID: {}, Wallet Address: {}", user_id, wallet_address),
         );
-        let config = Config::new(
-            user_id,
-            wallet_address.to_string(),
-            String::new(), // node_id is empty for now
-            orchestrator.environment().clone(),
-        );
         // Save the configuration file with the user ID and wallet address.
-        config.save(config_path).inspect_err(|e| {
-            print_error("Failed to save config", Some(&e.to_string()));
-        })?;
+        save_user(config_path, user_id, wallet_address, orchestrator.as_ref()).await?;
 
         // Guide user to next step
         print_success(
@@ -85,7 +88,11 @@ pub async fn register_user(
 
     // Otherwise, register the user with the orchestrator.
     let uuid = uuid::Uuid::new_v4().to_string();
-    match orchestrator.register_user(&uuid, wallet_address).await {
+    match retry(&RetryPolicy::for_operation(Operation::Register), || {
+        orchestrator.register_user(&uuid, wallet_address)
+    })
+    .await
+    {
         Ok(_) => {
             print_success(
                 "User registered successfully",
@@ -93,27 +100,13 @@ pub async fn register_user(
             );
         }
         Err(e) => {
-            // Check if this looks like an orchestrator traffic issue
-            if let Some(pretty_error) = e.to_pretty() {
-                print_error("Failed to register user", Some(&pretty_error));
-            } else {
-                print_error("Failed to register user", Some(&e.to_string()));
-            }
-
+            print_error("Failed to register user", Some(&e.to_string()));
             return Err(e.into());
         }
     }
 
     // Save the configuration file with the user ID and wallet address.
-    let config = Config::new(
-        uuid,
-        wallet_address.to_string(),
-        String::new(), // node_id is empty for now
-        orchestrator.environment().clone(),
-    );
-    config.save(config_path).inspect_err(|e| {
-        print_error("Failed to save config", Some(&e.to_string()));
-    })?;
+    save_user(config_path, uuid, wallet_address, orchestrator.as_ref()).await?;
 
     // Guide user to next step
     print_success(
@@ -139,7 +132,7 @@ pub async fn register_node(
     // Requires: a config file with a registered user.
     // If a node_id is provided, update the config with it and use it.
     // If no node_id is provided, generate a new one.
-    let mut config = Config::load_from_file(config_path).inspect_err(|e| {
+    let config = Config::load_from_file(config_path).inspect_err(|e| {
         print_error(
             "Failed to load config, please register a user first",
             Some(&e.to_string()),
@@ -154,10 +147,7 @@ pub async fn register_node(
     if let Some(node_id) = node_id {
         // If a node_id is provided, update the config with it.
         println!("Registering node ID: {}", node_id);
-        config.node_id = node_id.to_string();
-        config.save(config_path).inspect_err(|e| {
-            print_error("Failed to save updated config", Some(&e.to_string()));
-        })?;
+        save_node(config_path, node_id.to_string()).await?;
 
         // Guide user to next step
         print_success(
@@ -174,14 +164,14 @@ pub async fn register_node(
             "No node ID provided. Registering a new node in environment: {:?}",
             orchestrator.environment()
         );
-        match orchestrator.register_node(&config.user_id).await {
+        match retry(&RetryPolicy::for_operation(Operation::Register), || {
+            orchestrator.register_node(&config.user_id)
+        })
+        .await
+        {
             Ok(node_id) => {
                 // Update the config with the new node ID
-                let mut updated_config = config;
-                updated_config.node_id = node_id.clone();
-                updated_config.save(config_path).inspect_err(|e| {
-                    print_error("Failed to save updated config", Some(&e.to_string()));
-                })?;
+                save_node(config_path, node_id.clone()).await?;
 
                 // Guide user to next step
                 print_success(
@@ -195,13 +185,52 @@ pub async fn register_node(
                 Ok(())
             }
             Err(e) => {
-                print_error("Failed to register node", Some(&e.to_string()));
+                match e.server_error() {
+                    Some(ServerError::UserNotFound) => print_error(
+                        "Failed to register node",
+                        Some(
+                            "Your user is not known to the orchestrator. Register it again: nexus-cli register-user --wallet-address <your-wallet-address>",
+                        ),
+                    ),
+                    _ => print_error("Failed to register node", Some(&e.to_string())),
+                }
                 Err(e.into())
             }
         }
     }
 }
 
+/// Save a registered user to the config, keeping the rest of it. Any node belonged to the
+/// previous user, so it is cleared.
+async fn save_user(
+    config_path: &Path,
+    user_id: String,
+    wallet_address: &str,
+    orchestrator: &dyn Orchestrator,
+) -> Result<(), std::io::Error> {
+    Config::update(config_path, |config| {
+        config.user_id = user_id;
+        config.wallet_address = wallet_address.to_string();
+        config.node_id.clear();
+        config.environment = orchestrator.environment().to_string();
+    })
+    .await
+    .inspect_err(|e| {
+        print_error("Failed to save config", Some(&e.to_string()));
+    })?;
+    Ok(())
+}
+
+/// Save a registered node to the config, keeping the rest of it
+async fn save_node(config_path: &Path, node_id: String) -> Result<(), std::io::Error> {
+    Config::update(config_path, |config| config.node_id = node_id)
+        .await
+        .inspect_err(|e| {
+            print_error("Failed to save updated config", Some(&e.to_string()));
+        })?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -263,7 +292,7 @@ mod tests {
         let dir = tempdir().unwrap();
         let config_path = dir.path().join("config.json");
 
-        let wallet_address = "0xABCDEFabcdef1234567890123456789012345678";
+        let wallet_address = "0xabcdefabcdef1234567890123456789012345678";
         let user_id = "existing-user-id";
 
         // Write a pre-existing config with matching wallet and user_id
@@ -298,4 +327,28 @@ mod tests {
             wallet_address.to_lowercase()
         );
     }
+
+    #[tokio::test]
+    /// A mistyped mixed-case address is rejected before anything is registered.
+    async fn rejects_address_with_bad_checksum() {
+        use tempfile::tempdir;
+
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.json");
+
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator.expect_get_user().never();
+        orchestrator.expect_register_user().never();
+
+        let result = register_user(
+            "0x52908400098527886E0F7030069857D2E4169ee7",
+            &config_path,
+            Box::new(orchestrator),
+        )
+        .await;
+
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("0x52908400098527886E0F7030069857D2E4169EE7"));
+        assert!(!config_path.exists());
+    }
 }
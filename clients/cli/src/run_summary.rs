@@ -0,0 +1,255 @@
+//! Run statistics and the end-of-run summary
+//!
+//! The worker records how many tasks it fetched, proved and submitted, how many failed and
+//! how long each stage took in [`RunStats`], shared with the session. When the node stops,
+//! [`RunSummary`] is printed and optionally written as JSON for `--summary-file`, and its
+//! outcome decides the exit code of a `--max-tasks` batch run.
+
+use crate::atomic_write::write_atomic;
+use crate::consts::cli_consts::run_exit_codes;
+use crate::events::format_duration;
+use serde::Serialize;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Why the worker stopped by itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// All `--max-tasks` tasks were submitted
+    Completed,
+    /// Too many proofs in a row failed
+    ProvingFailures,
+}
+
+/// Counters and stage timings of a run, shared between the worker and the session
+#[derive(Debug)]
+pub struct RunStats {
+    started: Instant,
+    fetched: u32,
+    proven: u32,
+    submitted: u32,
+    proving_failures: u32,
+    submission_failures: u32,
+    fetch_time: Duration,
+    prove_time: Duration,
+    submit_time: Duration,
+    stop_reason: Option<StopReason>,
+}
+
+/// [`RunStats`] as shared by the worker and the session
+pub type SharedRunStats = Arc<Mutex<RunStats>>;
+
+impl RunStats {
+    pub fn new(started: Instant) -> Self {
+        Self {
+            started,
+            fetched: 0,
+            proven: 0,
+            submitted: 0,
+            proving_failures: 0,
+            submission_failures: 0,
+            fetch_time: Duration::ZERO,
+            prove_time: Duration::ZERO,
+            submit_time: Duration::ZERO,
+            stop_reason: None,
+        }
+    }
+
+    pub fn shared() -> SharedRunStats {
+        Arc::new(Mutex::new(Self::new(Instant::now())))
+    }
+
+    pub fn record_fetch(&mut self, took: Duration) {
+        self.fetched += 1;
+        self.fetch_time += took;
+    }
+
+    pub fn record_proof(&mut self, took: Duration, succeeded: bool) {
+        self.prove_time += took;
+        if succeeded {
+            self.proven += 1;
+        } else {
+            self.proving_failures += 1;
+        }
+    }
+
+    pub fn record_submission(&mut self, took: Duration, succeeded: bool) {
+        self.submit_time += took;
+        if succeeded {
+            self.submitted += 1;
+        } else {
+            self.submission_failures += 1;
+        }
+    }
+
+    pub fn stop(&mut self, reason: StopReason) {
+        self.stop_reason = Some(reason);
+    }
+
+    /// Summarize the run so far. A batch run that stopped without a reason was interrupted.
+    pub fn summary(&self, now: Instant, batch: bool) -> RunSummary {
+        let outcome = match self.stop_reason {
+            Some(StopReason::Completed) => RunOutcome::Completed,
+            Some(StopReason::ProvingFailures) => RunOutcome::ProvingFailures,
+            None if batch => RunOutcome::Interrupted,
+            None => RunOutcome::Stopped,
+        };
+        let elapsed = now.saturating_duration_since(self.started);
+        let hours = elapsed.as_secs_f64() / 3600.0;
+        RunSummary {
+            outcome,
+            exit_code: outcome.exit_code(),
+            tasks_fetched: self.fetched,
+            proofs_generated: self.proven,
+            proofs_submitted: self.submitted,
+            proving_failures: self.proving_failures,
+            submission_failures: self.submission_failures,
+            elapsed_secs: elapsed.as_secs_f64(),
+            fetch: StageTime::new(self.fetch_time, self.fetched),
+            prove: StageTime::new(self.prove_time, self.proven + self.proving_failures),
+            submit: StageTime::new(self.submit_time, self.submitted + self.submission_failures),
+            proofs_per_hour: if hours > 0.0 {
+                self.submitted as f64 / hours
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// How a run ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunOutcome {
+    /// All `--max-tasks` tasks were submitted
+    Completed,
+    /// A `--max-tasks` run was stopped before finishing
+    Interrupted,
+    /// Too many proofs in a row failed
+    ProvingFailures,
+    /// A run without `--max-tasks` was stopped, which is how it normally ends
+    Stopped,
+}
+
+impl RunOutcome {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Self::Completed | Self::Stopped => run_exit_codes::COMPLETED,
+            Self::Interrupted => run_exit_codes::INTERRUPTED,
+            Self::ProvingFailures => run_exit_codes::PROVING_FAILURES,
+        }
+    }
+}
+
+/// Time spent in one stage of the task lifecycle
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StageTime {
+    pub total_secs: f64,
+    pub average_secs: f64,
+}
+
+impl StageTime {
+    fn new(total: Duration, count: u32) -> Self {
+        let total_secs = total.as_secs_f64();
+        Self {
+            total_secs,
+            average_secs: if count > 0 {
+                total_secs / count as f64
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// End-of-run report, printed on exit and written by `--summary-file`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunSummary {
+    pub outcome: RunOutcome,
+    pub exit_code: i32,
+    pub tasks_fetched: u32,
+    pub proofs_generated: u32,
+    pub proofs_submitted: u32,
+    pub proving_failures: u32,
+    pub submission_failures: u32,
+    pub elapsed_secs: f64,
+    pub fetch: StageTime,
+    pub prove: StageTime,
+    pub submit: StageTime,
+    pub proofs_per_hour: f64,
+}
+
+impl RunSummary {
+    pub fn print(&self) {
+        crate::print_cmd_info!(
+            "Run summary",
+            "{} tasks fetched, {} proved, {} submitted, {} failed ({} proving, {} submitting) in {}",
+            self.tasks_fetched,
+            self.proofs_generated,
+            self.proofs_submitted,
+            self.proving_failures + self.submission_failures,
+            self.proving_failures,
+            self.submission_failures,
+            format_duration(Duration::from_secs_f64(self.elapsed_secs))
+        );
+        crate::print_cmd_info!(
+            "Stage times",
+            "fetch {:.1}s, prove {:.1}s, submit {:.1}s on average; {:.1} proofs per hour",
+            self.fetch.average_secs,
+            self.prove.average_secs,
+            self.submit.average_secs,
+            self.proofs_per_hour
+        );
+    }
+
+    /// Write the summary as JSON
+    pub fn write(&self, path: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        write_atomic(path, json.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_counts_stages_and_rates() {
+        let start = Instant::now();
+        let mut stats = RunStats::new(start);
+        stats.record_fetch(Duration::from_secs(2));
+        stats.record_proof(Duration::from_secs(10), true);
+        stats.record_submission(Duration::from_secs(1), true);
+        stats.record_fetch(Duration::from_secs(4));
+        stats.record_proof(Duration::from_secs(20), false);
+
+        let summary = stats.summary(start + Duration::from_secs(1800), false);
+        assert_eq!(summary.outcome, RunOutcome::Stopped);
+        assert_eq!(summary.exit_code, 0);
+        assert_eq!(summary.tasks_fetched, 2);
+        assert_eq!(summary.proofs_generated, 1);
+        assert_eq!(summary.proving_failures, 1);
+        assert_eq!(summary.fetch.average_secs, 3.0);
+        assert_eq!(summary.prove.total_secs, 30.0);
+        assert_eq!(summary.proofs_per_hour, 2.0);
+    }
+
+    #[test]
+    fn test_batch_outcomes_map_to_exit_codes() {
+        let start = Instant::now();
+        let mut stats = RunStats::new(start);
+        assert_eq!(
+            stats.summary(start, true).exit_code,
+            run_exit_codes::INTERRUPTED
+        );
+        stats.stop(StopReason::ProvingFailures);
+        assert_eq!(
+            stats.summary(start, true).exit_code,
+            run_exit_codes::PROVING_FAILURES
+        );
+        stats.stop(StopReason::Completed);
+        assert_eq!(stats.summary(start, true).outcome, RunOutcome::Completed);
+        assert_eq!(stats.summary(start, true).exit_code, 0);
+    }
+}
@@ -3,8 +3,9 @@
 use crate::environment::Environment;
 use crate::events::Event;
 use crate::orchestrator::OrchestratorClient;
+use crate::run_summary::SharedRunStats;
 use crate::workers::authenticated_worker::AuthenticatedWorker;
-use crate::workers::core::WorkerConfig;
+use crate::workers::core::{ProvingOptions, WorkerConfig};
 use ed25519_dalek::SigningKey;
 use tokio::sync::{broadcast, mpsc};
 use tokio::task::JoinHandle;
@@ -18,13 +19,16 @@ pub async fn start_authenticated_worker(
     shutdown: broadcast::Receiver<()>,
     environment: Environment,
     client_id: String,
+    num_workers: usize,
     max_tasks: Option<u32>,
+    stats: SharedRunStats,
+    proving: ProvingOptions,
 ) -> (
     mpsc::Receiver<Event>,
     Vec<JoinHandle<()>>,
     broadcast::Sender<()>,
 ) {
-    let config = WorkerConfig::new(environment, client_id);
+    let config = WorkerConfig::new(environment, client_id, num_workers, proving);
     let (event_sender, event_receiver) =
         mpsc::channel::<Event>(crate::consts::cli_consts::EVENT_QUEUE_SIZE);
 
@@ -38,6 +42,7 @@ pub async fn start_authenticated_worker(
         config,
         event_sender,
         max_tasks,
+        stats,
         shutdown_sender.clone(),
     );
 
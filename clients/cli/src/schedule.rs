@@ -0,0 +1,406 @@
+//! Proving schedule
+//!
+//! Lets nodes on shared machines prove only when the machine is otherwise free. Before each
+//! task is fetched the [`Scheduler`] checks the configured time windows (for example
+//! `weekdays 19:00-07:00`), the CPU used by other processes and whether the machine runs on
+//! battery. While any of them says no, the worker is reported as [`ProverState::Paused`] and
+//! checks again periodically, resuming on its own once proving is allowed. A task that is
+//! already being proven always runs to completion.
+
+use crate::consts::cli_consts::SCHEDULE_POLL_SECS;
+use crate::events::{Event, ProverState};
+use crate::system;
+use crate::workers::core::EventSender;
+use chrono::{Datelike, Local, NaiveDateTime, NaiveTime, Weekday};
+use clap::{ArgAction, Args};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::mem::discriminant;
+use std::str::FromStr;
+use std::time::Duration;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+/// When the node may fetch new tasks, from the `schedule` section of the config file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct ScheduleConfig {
+    /// Only prove within these windows; always when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub windows: Vec<TimeWindow>,
+    /// Pause while other processes use more than this percentage of the CPU
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_other_cpu_percent: Option<u8>,
+    /// Pause while the machine runs on battery
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pause_on_battery: bool,
+}
+
+impl ScheduleConfig {
+    /// Whether nothing would ever pause proving
+    pub fn is_unrestricted(&self) -> bool {
+        self.windows.is_empty() && self.max_other_cpu_percent.is_none() && !self.pause_on_battery
+    }
+
+    /// Why proving should pause at `now`, given the CPU usage of other processes in percent
+    /// and whether the machine is on battery
+    pub fn pause_reason(
+        &self,
+        now: NaiveDateTime,
+        other_cpu_percent: Option<f32>,
+        on_battery: bool,
+    ) -> Option<PauseReason> {
+        if !self.windows.is_empty() && !self.windows.iter().any(|window| window.contains(now)) {
+            return Some(PauseReason::OutsideWindows);
+        }
+        if self.pause_on_battery && on_battery {
+            return Some(PauseReason::OnBattery);
+        }
+        match (self.max_other_cpu_percent, other_cpu_percent) {
+            (Some(max), Some(percent)) if percent > max as f32 => {
+                Some(PauseReason::BusyCpu { percent })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Command-line options for the proving schedule. They replace the corresponding settings
+/// from the config file.
+#[derive(Args, Debug, Clone, Default)]
+pub struct ScheduleArgs {
+    /// Only fetch tasks within this time window, e.g. "weekdays 19:00-07:00" (repeatable)
+    #[arg(long = "schedule", value_name = "WINDOW")]
+    pub schedule: Vec<TimeWindow>,
+
+    /// Pause while other processes use more than this percentage of the CPU
+    #[arg(long = "max-other-cpu", value_name = "PERCENT", value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub max_other_cpu: Option<u8>,
+
+    /// Pause while the machine runs on battery
+    #[arg(long = "pause-on-battery", action = ArgAction::SetTrue)]
+    pub pause_on_battery: bool,
+}
+
+impl ScheduleArgs {
+    pub fn apply(&self, mut config: ScheduleConfig) -> ScheduleConfig {
+        if !self.schedule.is_empty() {
+            config.windows = self.schedule.clone();
+        }
+        if self.max_other_cpu.is_some() {
+            config.max_other_cpu_percent = self.max_other_cpu;
+        }
+        config.pause_on_battery |= self.pause_on_battery;
+        config
+    }
+}
+
+/// A daily time range on some days of the week, such as `weekdays 19:00-07:00`.
+///
+/// A range that ends at or before its start runs past midnight into the next day, so the
+/// example above covers Friday 19:00 to Saturday 07:00 but not Saturday evening.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TimeWindow {
+    /// Days the window starts on, indexed from Monday
+    days: [bool; 7],
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl TimeWindow {
+    pub fn contains(&self, now: NaiveDateTime) -> bool {
+        let (day, time) = (now.weekday(), now.time());
+        let starts_on = |day: Weekday| self.days[day.num_days_from_monday() as usize];
+        if self.start < self.end {
+            starts_on(day) && time >= self.start && time < self.end
+        } else {
+            (starts_on(day) && time >= self.start) || (starts_on(day.pred()) && time < self.end)
+        }
+    }
+}
+
+const WEEKDAYS: [bool; 7] = [true, true, true, true, true, false, false];
+const WEEKENDS: [bool; 7] = [false, false, false, false, false, true, true];
+const EVERY_DAY: [bool; 7] = [true; 7];
+
+fn parse_days(spec: &str) -> Result<[bool; 7], String> {
+    match spec.to_ascii_lowercase().as_str() {
+        "daily" | "everyday" => return Ok(EVERY_DAY),
+        "weekdays" => return Ok(WEEKDAYS),
+        "weekends" => return Ok(WEEKENDS),
+        _ => {}
+    }
+    let parse_day = |name: &str| {
+        Weekday::from_str(name.trim()).map_err(|_| format!("unknown day '{}'", name.trim()))
+    };
+    let mut days = [false; 7];
+    for part in spec.split(',') {
+        let (first, last) = match part.split_once('-') {
+            Some((first, last)) => (parse_day(first)?, parse_day(last)?),
+            None => (parse_day(part)?, parse_day(part)?),
+        };
+        // Ranges may wrap around the week, e.g. fri-mon
+        let mut day = first;
+        loop {
+            days[day.num_days_from_monday() as usize] = true;
+            if day == last {
+                break;
+            }
+            day = day.succ();
+        }
+    }
+    Ok(days)
+}
+
+fn parse_time(time: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(time.trim(), "%H:%M")
+        .map_err(|_| format!("invalid time '{}', expected HH:MM", time.trim()))
+}
+
+impl FromStr for TimeWindow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (days, range) = match s.split_once(char::is_whitespace) {
+            Some((days, range)) => (parse_days(days)?, range),
+            None => (EVERY_DAY, s),
+        };
+        let (start, end) = range
+            .split_once(['-', '–'])
+            .ok_or_else(|| format!("invalid time range '{}', expected HH:MM-HH:MM", range))?;
+        Ok(Self {
+            days,
+            start: parse_time(start)?,
+            end: parse_time(end)?,
+        })
+    }
+}
+
+impl TryFrom<String> for TimeWindow {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<TimeWindow> for String {
+    fn from(window: TimeWindow) -> Self {
+        window.to_string()
+    }
+}
+
+impl fmt::Display for TimeWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let days = match self.days {
+            EVERY_DAY => "daily".to_string(),
+            WEEKDAYS => "weekdays".to_string(),
+            WEEKENDS => "weekends".to_string(),
+            days => (0..7)
+                .filter(|&index| days[index])
+                .map(|index| {
+                    Weekday::try_from(index as u8)
+                        .map(|day| day.to_string().to_lowercase())
+                        .unwrap_or_default()
+                })
+                .collect::<Vec<_>>()
+                .join(","),
+        };
+        write!(
+            f,
+            "{} {}-{}",
+            days,
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M")
+        )
+    }
+}
+
+/// Why proving is paused
+#[derive(Debug, Clone, PartialEq)]
+pub enum PauseReason {
+    OutsideWindows,
+    OnBattery,
+    BusyCpu { percent: f32 },
+}
+
+impl fmt::Display for PauseReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PauseReason::OutsideWindows => write!(f, "outside the scheduled proving hours"),
+            PauseReason::OnBattery => write!(f, "running on battery"),
+            PauseReason::BusyCpu { percent } => {
+                write!(f, "other processes are using {:.0}% of the CPU", percent)
+            }
+        }
+    }
+}
+
+/// Holds the worker back while the schedule does not allow proving
+pub struct Scheduler {
+    config: ScheduleConfig,
+    system: System,
+    paused: Option<PauseReason>,
+}
+
+impl Scheduler {
+    pub fn new(config: ScheduleConfig) -> Self {
+        Self {
+            config,
+            system: System::new(),
+            paused: None,
+        }
+    }
+
+    /// Wait until proving is allowed, reporting pauses and resumes as state changes
+    pub async fn wait_until_allowed(&mut self, events: &EventSender) {
+        if self.config.is_unrestricted() {
+            return;
+        }
+        loop {
+            let Some(reason) = self.check().await else {
+                if self.paused.take().is_some() {
+                    events
+                        .send_event(Event::state_change(
+                            ProverState::Waiting,
+                            "Resuming: proving is allowed again".to_string(),
+                        ))
+                        .await;
+                }
+                return;
+            };
+            let changed = self
+                .paused
+                .as_ref()
+                .is_none_or(|paused| discriminant(paused) != discriminant(&reason));
+            if changed {
+                events
+                    .send_event(Event::state_change(
+                        ProverState::Paused,
+                        format!("Paused: {}", reason),
+                    ))
+                    .await;
+            }
+            self.paused = Some(reason);
+            tokio::time::sleep(Duration::from_secs(SCHEDULE_POLL_SECS)).await;
+        }
+    }
+
+    async fn check(&mut self) -> Option<PauseReason> {
+        let other_cpu_percent = match self.config.max_other_cpu_percent {
+            Some(_) => Some(self.other_cpu_percent().await),
+            None => None,
+        };
+        let on_battery = self.config.pause_on_battery && system::on_battery_power();
+        self.config
+            .pause_reason(Local::now().naive_local(), other_cpu_percent, on_battery)
+    }
+
+    /// CPU usage of everything except this process and its prover children, in percent
+    async fn other_cpu_percent(&mut self) -> f32 {
+        // Usage is measured between two refreshes
+        self.refresh_cpu();
+        tokio::time::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL).await;
+        self.refresh_cpu();
+
+        let current_pid = Pid::from(std::process::id() as usize);
+        // Process usage is relative to one core, global usage to all of them
+        let own: f32 = self
+            .system
+            .processes()
+            .values()
+            .filter(|process| process.pid() == current_pid || process.parent() == Some(current_pid))
+            .map(|process| process.cpu_usage())
+            .sum();
+        let cores = self.system.cpus().len().max(1) as f32;
+        (self.system.global_cpu_usage() - own / cores).max(0.0)
+    }
+
+    fn refresh_cpu(&mut self) {
+        self.system.refresh_cpu_usage();
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing().with_cpu(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, time: &str) -> NaiveDateTime {
+        // 2025-03-03 is a Monday
+        NaiveDate::from_ymd_opt(2025, 3, day)
+            .unwrap()
+            .and_time(parse_time(time).unwrap())
+    }
+
+    #[test]
+    fn test_window_crossing_midnight() {
+        let window: TimeWindow = "weekdays 19:00-07:00".parse().unwrap();
+        assert!(window.contains(at(3, "19:00")));
+        assert!(window.contains(at(4, "06:59")));
+        assert!(!window.contains(at(4, "07:00")));
+        assert!(!window.contains(at(3, "12:00")));
+        // Friday night runs into Saturday morning, but Saturday evening is off
+        assert!(window.contains(at(8, "06:00")));
+        assert!(!window.contains(at(8, "20:00")));
+        // Monday morning belongs to Sunday night, which is not a weekday
+        assert!(!window.contains(at(3, "06:00")));
+    }
+
+    #[test]
+    fn test_window_parsing_and_display() {
+        let window: TimeWindow = "sat-sun,wed 09:30–17:00".parse().unwrap();
+        assert_eq!(window.to_string(), "wed,sat,sun 09:30-17:00");
+        assert!(window.contains(at(5, "10:00")));
+        assert!(!window.contains(at(6, "10:00")));
+
+        let daily: TimeWindow = "22:00-06:00".parse().unwrap();
+        assert_eq!(daily.to_string(), "daily 22:00-06:00");
+        assert_eq!(daily.to_string().parse::<TimeWindow>().unwrap(), daily);
+
+        assert!("weekdays 25:00-07:00".parse::<TimeWindow>().is_err());
+        assert!("someday 19:00-07:00".parse::<TimeWindow>().is_err());
+        assert!("weekdays 19:00".parse::<TimeWindow>().is_err());
+    }
+
+    #[test]
+    fn test_pause_reason() {
+        let config: ScheduleConfig = serde_json::from_str(
+            r#"{"windows": ["weekdays 19:00-07:00"], "max_other_cpu_percent": 50, "pause_on_battery": true}"#,
+        )
+        .unwrap();
+        assert!(!config.is_unrestricted());
+
+        let evening = at(3, "20:00");
+        assert_eq!(config.pause_reason(evening, Some(10.0), false), None);
+        assert_eq!(
+            config.pause_reason(at(3, "12:00"), Some(10.0), false),
+            Some(PauseReason::OutsideWindows)
+        );
+        assert_eq!(
+            config.pause_reason(evening, Some(10.0), true),
+            Some(PauseReason::OnBattery)
+        );
+        assert_eq!(
+            config.pause_reason(evening, Some(75.0), false),
+            Some(PauseReason::BusyCpu { percent: 75.0 })
+        );
+
+        let args = ScheduleArgs {
+            schedule: vec!["daily 00:00-00:00".parse().unwrap()],
+            max_other_cpu: None,
+            pause_on_battery: false,
+        };
+        let overridden = args.apply(config);
+        assert_eq!(
+            overridden.pause_reason(at(3, "12:00"), Some(10.0), false),
+            None
+        );
+        assert_eq!(overridden.max_other_cpu_percent, Some(50));
+    }
+}
@@ -3,7 +3,10 @@
 use super::{
     SessionData,
     messages::{print_session_exit_success, print_session_shutdown, print_session_starting},
+    setup::add_configured_sinks,
 };
+use crate::daemon::{Daemon, DaemonArgs};
+use crate::event_bus::ConsoleSink;
 use crate::print_cmd_info;
 use crate::version::checker::check_for_new_version;
 use std::error::Error;
@@ -11,17 +14,22 @@ use std::error::Error;
 /// Runs the application in headless mode
 ///
 /// This function handles:
-/// 1. Console event logging
-/// 2. Ctrl+C shutdown handling
+/// 1. Console event logging, plus any sinks selected in the config
+/// 2. Ctrl+C and SIGTERM shutdown handling
 /// 3. Event loop management
+/// 4. systemd notifications, the PID file and the health endpoint
 ///
 /// # Arguments
 /// * `session` - Session data from setup
+/// * `daemon_args` - Daemon options from the command line
 ///
 /// # Returns
 /// * `Ok(())` - Headless mode completed successfully
 /// * `Err` - Headless mode failed
-pub async fn run_headless_mode(mut session: SessionData) -> Result<(), Box<dyn Error>> {
+pub async fn run_headless_mode(
+    session: SessionData,
+    daemon_args: &DaemonArgs,
+) -> Result<(), Box<dyn Error>> {
     // Print session start message
     print_session_starting("headless", session.node_id);
 
@@ -34,10 +42,21 @@ pub async fn run_headless_mode(mut session: SessionData) -> Result<(), Box<dyn E
         print_cmd_info!("Version check", "{}", message);
     }
 
-    // Trigger shutdown on Ctrl+C
+    // Print events to the console, unless the config asks for JSON output instead
+    let mut events = session.events;
+    add_configured_sinks(&mut events, &session.event_sinks, false);
+    if !session.event_sinks.iter().any(|sink| sink.uses_stdout()) {
+        events.add_sink(Box::new(ConsoleSink::plain()));
+    }
+    let daemon = Daemon::start(daemon_args).await?;
+    events.add_lossless_sink(daemon.sink());
+    events.start();
+    daemon.ready();
+
+    // Trigger shutdown on Ctrl+C, or on SIGTERM from a service manager
     let shutdown_sender_clone = session.shutdown_sender.clone();
     tokio::spawn(async move {
-        if tokio::signal::ctrl_c().await.is_ok() {
+        if shutdown_signal().await {
             let _ = shutdown_sender_clone.send(());
         }
     });
@@ -45,27 +64,36 @@ pub async fn run_headless_mode(mut session: SessionData) -> Result<(), Box<dyn E
     let mut shutdown_receiver = session.shutdown_sender.subscribe();
     let mut max_tasks_shutdown_receiver = session.max_tasks_shutdown_sender.subscribe();
 
-    // Event loop: log events to console until shutdown
-    loop {
-        tokio::select! {
-            Some(event) = session.event_receiver.recv() => {
-                println!("{}", event);
-            }
-            _ = shutdown_receiver.recv() => {
-                break;
-            }
-            _ = max_tasks_shutdown_receiver.recv() => {
-                break;
-            }
-        }
+    // Wait until shutdown while the sinks handle events
+    tokio::select! {
+        _ = shutdown_receiver.recv() => {}
+        _ = max_tasks_shutdown_receiver.recv() => {}
     }
 
     // Wait for workers to finish
+    daemon.stopping();
     print_session_shutdown();
     for handle in session.join_handles {
         let _ = handle.await;
     }
+    events.shutdown().await;
+    drop(daemon);
     print_session_exit_success();
 
     Ok(())
 }
+
+/// Wait for Ctrl+C or, on Unix, SIGTERM, returning whether one arrived
+async fn shutdown_signal() -> bool {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        if let Ok(mut terminate) = signal(SignalKind::terminate()) {
+            return tokio::select! {
+                result = tokio::signal::ctrl_c() => result.is_ok(),
+                _ = terminate.recv() => true,
+            };
+        }
+    }
+    tokio::signal::ctrl_c().await.is_ok()
+}
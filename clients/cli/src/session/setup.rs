@@ -1,29 +1,36 @@
 //! Session setup and initialization
 
 use crate::analytics::set_wallet_address_for_reporting;
+use crate::benchmark::{self, Calibration};
 use crate::config::Config;
 use crate::environment::Environment;
-use crate::events::Event;
+use crate::event_bus::{EventBus, SinkConfig};
+use crate::nexus_orchestrator::TaskDifficulty;
 use crate::orchestrator::OrchestratorClient;
+use crate::run_summary::{RunStats, SharedRunStats};
 use crate::runtime::start_authenticated_worker;
-use crate::system;
+use crate::workers::core::ProvingOptions;
 use ed25519_dalek::SigningKey;
 use std::error::Error;
 use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::broadcast;
 use tokio::task::JoinHandle;
 
 /// Session data for both TUI and headless modes
 #[derive(Debug)]
 pub struct SessionData {
-    /// Event receiver for worker events
-    pub event_receiver: mpsc::Receiver<Event>,
+    /// Event bus carrying worker events, started once the mode has added its sinks
+    pub events: EventBus,
+    /// Event sinks selected in the config file
+    pub event_sinks: Vec<SinkConfig>,
     /// Join handles for worker tasks
     pub join_handles: Vec<JoinHandle<()>>,
     /// Shutdown sender to stop all workers
     pub shutdown_sender: broadcast::Sender<()>,
     /// Shutdown sender for max tasks completion
     pub max_tasks_shutdown_sender: broadcast::Sender<()>,
+    /// Counters and stage timings for the end-of-run summary
+    pub run_stats: SharedRunStats,
     /// Node ID
     pub node_id: u64,
     /// Orchestrator client
@@ -61,6 +68,25 @@ pub fn warn_memory_configuration(max_threads: Option<u32>) {
     }
 }
 
+/// Subscribe the event sinks selected in the config file. Sinks that write to stdout are
+/// skipped when the TUI owns the terminal.
+pub fn add_configured_sinks(events: &mut EventBus, configs: &[SinkConfig], tui: bool) {
+    let (skipped, configs): (Vec<_>, Vec<_>) = configs
+        .iter()
+        .cloned()
+        .partition(|config| tui && config.uses_stdout());
+    if !skipped.is_empty() {
+        crate::print_cmd_warn!(
+            "Event sinks",
+            "Skipping {} stdout sink(s) while the dashboard is running; use --headless to enable them",
+            skipped.len()
+        );
+    }
+    for (config, e) in events.add_configured_sinks(&configs) {
+        crate::print_cmd_warn!("Event sinks", "Could not start {:?}: {}", config, e);
+    }
+}
+
 /// Sets up an authenticated worker session
 ///
 /// This function handles all the common setup required for both TUI and headless modes:
@@ -73,26 +99,36 @@ pub fn warn_memory_configuration(max_threads: Option<u32>) {
 /// * `config` - Resolved configuration with node_id and client_id
 /// * `env` - Environment to connect to
 /// * `max_threads` - Optional maximum number of threads for proving
+/// * `max_difficulty` - Maximum difficulty of tasks to request
+/// * `calibration` - Benchmark calibration loaded next to the config file, if any
+/// * `proving` - Local verification and proof archive options
 ///
 /// # Returns
 /// * `Ok(SessionData)` - Successfully set up session
 /// * `Err` - Session setup failed
+#[allow(clippy::too_many_arguments)]
 pub async fn setup_session(
     config: Config,
     env: Environment,
     check_mem: bool,
     max_threads: Option<u32>,
     max_tasks: Option<u32>,
+    max_difficulty: TaskDifficulty,
+    calibration: Option<Calibration>,
+    proving: ProvingOptions,
 ) -> Result<SessionData, Box<dyn Error>> {
     let node_id = config.node_id.parse::<u64>()?;
     let client_id = config.user_id;
+    let event_sinks = config.event_sinks;
 
     // Create a signing key for the prover
     let mut csprng = rand_core::OsRng;
     let signing_key: SigningKey = SigningKey::generate(&mut csprng);
 
     // Create orchestrator client
-    let orchestrator_client = OrchestratorClient::new(env.clone());
+    let orchestrator_client = OrchestratorClient::new(env.clone())
+        .with_max_difficulty(max_difficulty)
+        .with_calibration(calibration.clone());
 
     // Warn the user if the memory demands of their configuration is risky
     if check_mem {
@@ -102,8 +138,8 @@ pub async fn setup_session(
     // Clamp the number of workers to [1,8]. Keep this low for now to avoid rate limiting.
     let num_workers: usize = max_threads.unwrap_or(1).clamp(1, 8) as usize;
 
-    // Estimate GFLOP/s
-    let gflops = system::estimate_peak_gflops(num_workers);
+    // Estimate GFLOP/s, from the benchmark calibration if available
+    let gflops = benchmark::effective_gflops(calibration.as_ref(), num_workers);
 
     // Create shutdown channel - only one shutdown signal needed
     let (shutdown_sender, _) = broadcast::channel(1);
@@ -112,6 +148,7 @@ pub async fn setup_session(
     set_wallet_address_for_reporting(config.wallet_address.clone());
 
     // Start authenticated worker (only mode we support now)
+    let run_stats = RunStats::shared();
     let (event_receiver, join_handles, max_tasks_shutdown_sender) = start_authenticated_worker(
         node_id,
         signing_key,
@@ -119,15 +156,20 @@ pub async fn setup_session(
         shutdown_sender.subscribe(),
         env,
         client_id,
+        num_workers,
         max_tasks,
+        run_stats.clone(),
+        proving,
     )
     .await;
 
     Ok(SessionData {
-        event_receiver,
+        events: EventBus::new(event_receiver),
+        event_sinks,
         join_handles,
         shutdown_sender,
         max_tasks_shutdown_sender,
+        run_stats,
         node_id,
         orchestrator: orchestrator_client,
         num_workers,
@@ -3,7 +3,10 @@
 use super::{
     SessionData,
     messages::{print_session_exit_success, print_session_shutdown, print_session_starting},
+    setup::add_configured_sinks,
 };
+use crate::consts::cli_consts::EVENT_QUEUE_SIZE;
+use crate::event_bus::TuiSink;
 use crate::orchestrator::Orchestrator;
 use crate::ui::{self, UIConfig};
 use crate::version::checker::check_for_new_version;
@@ -14,6 +17,7 @@ use crossterm::{
 };
 use ratatui::{Terminal, backend::CrosstermBackend};
 use std::{error::Error, io};
+use tokio::sync::mpsc;
 
 /// Runs the application in TUI mode
 ///
@@ -50,6 +54,13 @@ pub async fn run_tui_mode(
             (false, None)
         };
 
+    // Feed events to the dashboard and any sinks selected in the config
+    let mut events = session.events;
+    add_configured_sinks(&mut events, &session.event_sinks, true);
+    let (tui_sender, tui_receiver) = mpsc::channel(EVENT_QUEUE_SIZE);
+    events.add_lossless_sink(Box::new(TuiSink::new(tui_sender)));
+    events.start();
+
     // Terminal setup
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -66,12 +77,13 @@ pub async fn run_tui_mode(
         version_update_available,
         latest_version,
         session.gflops,
+        session.orchestrator.max_difficulty(),
     );
 
     let app = ui::App::new(
         Some(session.node_id),
         session.orchestrator.environment().clone(),
-        session.event_receiver,
+        tui_receiver,
         session.shutdown_sender.clone(),
         session.max_tasks_shutdown_sender.subscribe(),
         ui_config,
@@ -96,6 +108,7 @@ pub async fn run_tui_mode(
     for handle in session.join_handles {
         let _ = handle.await;
     }
+    events.shutdown().await;
     print_session_exit_success();
 
     Ok(())
@@ -0,0 +1,437 @@
+//! Non-interactive setup
+//!
+//! `nexus-network setup` brings a machine from nothing to ready-to-start in one step, for
+//! provisioning tools such as cloud-init or Ansible. It registers the wallet's user and,
+//! when asked, a node, reconciling an existing config file with what the orchestrator knows.
+//! Running it again changes nothing. The outcome is printed as a single JSON object, and
+//! each failure mode exits with its own code from [`setup_exit_codes`].
+
+use crate::config::Config;
+use crate::consts::cli_consts::setup_exit_codes;
+use crate::keys::{self, AddressError};
+use crate::network::{Operation, RetryPolicy, retry};
+use crate::orchestrator::Orchestrator;
+use crate::orchestrator::error::{OrchestratorError, ServerError};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SetupError {
+    #[error("Invalid Ethereum wallet address: {0}")]
+    InvalidWallet(#[from] AddressError),
+
+    #[error("Failed to read config {}: {error}", path.display())]
+    ConfigUnreadable {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+
+    #[error("Failed to write config {}: {error}", path.display())]
+    ConfigUnwritable {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+
+    #[error("Config is for wallet {existing}; run `nexus-network logout` to set up another")]
+    ConfigWalletMismatch { existing: String },
+
+    #[error("Orchestrator request failed: {0}")]
+    Orchestrator(#[from] OrchestratorError),
+
+    #[error("Node {0} is not known to the orchestrator")]
+    NodeNotFound(u64),
+
+    #[error("Node {node_id} belongs to wallet {owner}")]
+    NodeWalletMismatch { node_id: u64, owner: String },
+}
+
+impl SetupError {
+    /// Process exit code for this failure
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            SetupError::InvalidWallet(_) => setup_exit_codes::INVALID_WALLET,
+            SetupError::ConfigUnreadable { .. } => setup_exit_codes::CONFIG_UNREADABLE,
+            SetupError::ConfigUnwritable { .. } => setup_exit_codes::CONFIG_UNWRITABLE,
+            SetupError::ConfigWalletMismatch { .. } => setup_exit_codes::CONFIG_WALLET_MISMATCH,
+            SetupError::Orchestrator(_) => setup_exit_codes::ORCHESTRATOR,
+            SetupError::NodeNotFound(_) => setup_exit_codes::NODE_NOT_FOUND,
+            SetupError::NodeWalletMismatch { .. } => setup_exit_codes::NODE_WALLET_MISMATCH,
+        }
+    }
+
+    /// Stable identifier of the failure mode, for scripts
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SetupError::InvalidWallet(_) => "invalid_wallet",
+            SetupError::ConfigUnreadable { .. } => "config_unreadable",
+            SetupError::ConfigUnwritable { .. } => "config_unwritable",
+            SetupError::ConfigWalletMismatch { .. } => "config_wallet_mismatch",
+            SetupError::Orchestrator(_) => "orchestrator",
+            SetupError::NodeNotFound(_) => "node_not_found",
+            SetupError::NodeWalletMismatch { .. } => "node_wallet_mismatch",
+        }
+    }
+
+    /// JSON description of the failure, printed in place of a [`SetupReport`]
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "status": "error",
+            "kind": self.kind(),
+            "exit_code": self.exit_code(),
+            "error": self.to_string(),
+        })
+    }
+}
+
+/// Which node `setup` should leave in the config
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeRequest {
+    /// Keep the configured node, if any, after checking it belongs to the wallet
+    Keep,
+    /// Link this existing node
+    Link(u64),
+    /// Keep the configured node if it belongs to the wallet, otherwise register a new one
+    Ensure,
+}
+
+/// What `setup` did to reach the requested state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SetupAction {
+    /// Already in place
+    Unchanged,
+    /// Known to the orchestrator, now recorded in the config
+    Linked,
+    /// Newly registered with the orchestrator
+    Registered,
+    /// No node requested and none configured
+    None,
+}
+
+/// Result of a successful `setup`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SetupReport {
+    pub status: &'static str,
+    pub config_path: PathBuf,
+    pub wallet_address: String,
+    pub user_id: String,
+    pub user: SetupAction,
+    pub node_id: Option<String>,
+    pub node: SetupAction,
+    /// Whether the config file was written
+    pub config_changed: bool,
+}
+
+/// Register the wallet's user and the requested node, and save them to the config at
+/// `config_path`. Safe to repeat: steps already done are only checked.
+pub async fn setup(
+    wallet_address: &str,
+    node: NodeRequest,
+    config_path: &Path,
+    orchestrator: &dyn Orchestrator,
+) -> Result<SetupReport, SetupError> {
+    let wallet_address = keys::parse_eth_address(wallet_address)?;
+
+    let existing = if config_path.exists() {
+        Some(
+            Config::load_from_file(config_path).map_err(|error| SetupError::ConfigUnreadable {
+                path: config_path.to_path_buf(),
+                error,
+            })?,
+        )
+    } else {
+        None
+    };
+    if let Some(existing) = &existing {
+        if !existing.wallet_address.is_empty()
+            && !existing
+                .wallet_address
+                .eq_ignore_ascii_case(&wallet_address)
+        {
+            return Err(SetupError::ConfigWalletMismatch {
+                existing: existing.wallet_address.clone(),
+            });
+        }
+    }
+    let mut config = existing.clone().unwrap_or_else(|| {
+        Config::new(
+            String::new(),
+            String::new(),
+            String::new(),
+            orchestrator.environment().clone(),
+        )
+    });
+    config.wallet_address = wallet_address.clone();
+
+    // The orchestrator's record of the user wins over the config file
+    let user = match retry(&RetryPolicy::for_operation(Operation::GetUser), || {
+        orchestrator.get_user(&wallet_address)
+    })
+    .await
+    {
+        Ok(user_id) => {
+            let action = if config.user_id == user_id {
+                SetupAction::Unchanged
+            } else {
+                SetupAction::Linked
+            };
+            config.user_id = user_id;
+            action
+        }
+        Err(e) if e.server_error().is_some_and(ServerError::is_not_found) => {
+            let user_id = uuid::Uuid::new_v4().to_string();
+            retry(&RetryPolicy::for_operation(Operation::Register), || {
+                orchestrator.register_user(&user_id, &wallet_address)
+            })
+            .await?;
+            config.user_id = user_id;
+            SetupAction::Registered
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let configured_node = config.node_id.parse::<u64>().ok();
+    let node = match node {
+        NodeRequest::Link(node_id) => {
+            check_node_owner(orchestrator, node_id, &wallet_address).await?;
+            let action = if configured_node == Some(node_id) {
+                SetupAction::Unchanged
+            } else {
+                SetupAction::Linked
+            };
+            config.node_id = node_id.to_string();
+            action
+        }
+        NodeRequest::Keep => match configured_node {
+            // A configured node that is gone or another wallet's is an error to report, not
+            // something to drop without being asked
+            Some(node_id) => {
+                check_node_owner(orchestrator, node_id, &wallet_address).await?;
+                SetupAction::Unchanged
+            }
+            None => SetupAction::None,
+        },
+        NodeRequest::Ensure => {
+            let keep = match configured_node {
+                Some(node_id) => {
+                    match check_node_owner(orchestrator, node_id, &wallet_address).await {
+                        Ok(()) => true,
+                        Err(
+                            SetupError::NodeNotFound(_) | SetupError::NodeWalletMismatch { .. },
+                        ) => false,
+                        Err(e) => return Err(e),
+                    }
+                }
+                None => false,
+            };
+            if keep {
+                SetupAction::Unchanged
+            } else {
+                config.node_id = retry(&RetryPolicy::for_operation(Operation::Register), || {
+                    orchestrator.register_node(&config.user_id)
+                })
+                .await?;
+                SetupAction::Registered
+            }
+        }
+    };
+
+    let config_changed = existing.as_ref() != Some(&config);
+    if config_changed {
+        // Re-read the file under its lock, so changes other processes made meanwhile stay
+        Config::update(config_path, |latest| {
+            latest.environment = config.environment.clone();
+            latest.user_id = config.user_id.clone();
+            latest.wallet_address = config.wallet_address.clone();
+            latest.node_id = config.node_id.clone();
+        })
+        .await
+        .map_err(|error| SetupError::ConfigUnwritable {
+            path: config_path.to_path_buf(),
+            error,
+        })?;
+    }
+
+    Ok(SetupReport {
+        status: "ok",
+        config_path: config_path.to_path_buf(),
+        wallet_address,
+        user_id: config.user_id,
+        user,
+        node_id: Some(config.node_id).filter(|node_id| !node_id.is_empty()),
+        node,
+        config_changed,
+    })
+}
+
+/// Check that a node exists and belongs to the wallet
+async fn check_node_owner(
+    orchestrator: &dyn Orchestrator,
+    node_id: u64,
+    wallet_address: &str,
+) -> Result<(), SetupError> {
+    let node_id_str = node_id.to_string();
+    let owner = retry(&RetryPolicy::for_operation(Operation::GetNode), || {
+        orchestrator.get_node(&node_id_str)
+    })
+    .await
+    .map_err(|e| {
+        if e.server_error().is_some_and(ServerError::is_not_found) {
+            SetupError::NodeNotFound(node_id)
+        } else {
+            e.into()
+        }
+    })?;
+    if owner.eq_ignore_ascii_case(wallet_address) {
+        Ok(())
+    } else {
+        Err(SetupError::NodeWalletMismatch { node_id, owner })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environment::Environment;
+    use crate::orchestrator::MockOrchestrator;
+    use tempfile::tempdir;
+
+    const WALLET: &str = "0x52908400098527886E0F7030069857D2E4169EE7";
+
+    fn not_found() -> OrchestratorError {
+        OrchestratorError::Http {
+            status: 404,
+            message: "not found".to_string(),
+            headers: std::collections::HashMap::new(),
+        }
+    }
+
+    fn orchestrator() -> MockOrchestrator {
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator
+            .expect_environment()
+            .return_const(Environment::Production);
+        orchestrator
+    }
+
+    #[tokio::test]
+    async fn test_setup_registers_once_then_changes_nothing() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+
+        let mut first = orchestrator();
+        first.expect_get_user().returning(|_| Err(not_found()));
+        first
+            .expect_register_user()
+            .times(1)
+            .returning(|_, _| Ok(()));
+        first
+            .expect_register_node()
+            .times(1)
+            .returning(|_| Ok("42".to_string()));
+        let report = setup(&WALLET.to_lowercase(), NodeRequest::Ensure, &path, &first)
+            .await
+            .unwrap();
+        assert_eq!(report.user, SetupAction::Registered);
+        assert_eq!(report.node, SetupAction::Registered);
+        assert_eq!(report.node_id.as_deref(), Some("42"));
+        assert!(report.config_changed);
+
+        let config = Config::load_from_file(&path).unwrap();
+        assert_eq!(config.wallet_address, WALLET);
+        let user_id = config.user_id.clone();
+
+        let mut second = orchestrator();
+        second
+            .expect_get_user()
+            .returning(move |_| Ok(user_id.clone()));
+        second
+            .expect_get_node()
+            .returning(|_| Ok(WALLET.to_lowercase()));
+        second.expect_register_user().never();
+        second.expect_register_node().never();
+        let report = setup(WALLET, NodeRequest::Ensure, &path, &second)
+            .await
+            .unwrap();
+        assert_eq!(report.user, SetupAction::Unchanged);
+        assert_eq!(report.node, SetupAction::Unchanged);
+        assert!(!report.config_changed);
+    }
+
+    #[tokio::test]
+    async fn test_setup_failures_have_distinct_exit_codes() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+
+        let error = setup("0x1234", NodeRequest::Keep, &path, &orchestrator())
+            .await
+            .unwrap_err();
+        assert_eq!(error.exit_code(), setup_exit_codes::INVALID_WALLET);
+
+        let mut other_owner = orchestrator();
+        other_owner
+            .expect_get_user()
+            .returning(|_| Ok("user".to_string()));
+        other_owner
+            .expect_get_node()
+            .returning(|_| Ok("0x0000000000000000000000000000000000000001".to_string()));
+        let error = setup(WALLET, NodeRequest::Link(7), &path, &other_owner)
+            .await
+            .unwrap_err();
+        assert_eq!(error.exit_code(), setup_exit_codes::NODE_WALLET_MISMATCH);
+        assert_eq!(error.to_json()["kind"], "node_wallet_mismatch");
+        assert!(!path.exists());
+
+        let mut missing = orchestrator();
+        missing
+            .expect_get_user()
+            .returning(|_| Ok("user".to_string()));
+        missing.expect_get_node().returning(|_| Err(not_found()));
+        let error = setup(WALLET, NodeRequest::Link(7), &path, &missing)
+            .await
+            .unwrap_err();
+        assert_eq!(error.exit_code(), setup_exit_codes::NODE_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_setup_reports_a_stale_configured_node() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        Config::update(&path, |config| {
+            config.user_id = "user".to_string();
+            config.wallet_address = WALLET.to_string();
+            config.node_id = "7".to_string();
+        })
+        .await
+        .unwrap();
+
+        let mut missing = orchestrator();
+        missing
+            .expect_get_user()
+            .returning(|_| Ok("user".to_string()));
+        missing.expect_get_node().returning(|_| Err(not_found()));
+        missing.expect_register_node().never();
+        let error = setup(WALLET, NodeRequest::Keep, &path, &missing)
+            .await
+            .unwrap_err();
+        assert_eq!(error.exit_code(), setup_exit_codes::NODE_NOT_FOUND);
+        assert_eq!(Config::load_from_file(&path).unwrap().node_id, "7");
+
+        // Asking for a node replaces the stale one
+        let mut replace = orchestrator();
+        replace
+            .expect_get_user()
+            .returning(|_| Ok("user".to_string()));
+        replace.expect_get_node().returning(|_| Err(not_found()));
+        replace
+            .expect_register_node()
+            .times(1)
+            .returning(|_| Ok("8".to_string()));
+        let report = setup(WALLET, NodeRequest::Ensure, &path, &replace)
+            .await
+            .unwrap();
+        assert_eq!(report.node, SetupAction::Registered);
+        assert_eq!(report.node_id.as_deref(), Some("8"));
+    }
+}
@@ -6,7 +6,7 @@ use std::process;
 use std::sync::OnceLock;
 use std::thread::available_parallelism;
 use std::time::Instant;
-use sysinfo::{CpuRefreshKind, RefreshKind, System};
+use sysinfo::{Components, CpuRefreshKind, RefreshKind, System};
 
 const NUM_TESTS: u64 = 1_000_000;
 const OPERATIONS_PER_ITERATION: u64 = 4; // sin, add, multiply, divide
@@ -173,6 +173,74 @@ pub fn total_memory_gb() -> f64 {
     total_memory as f64 / 1024.0 / 1024.0 / 1024.0 // Convert to GB (binary)
 }
 
+/// Memory available for new allocations, in bytes.
+pub fn available_memory_bytes() -> u64 {
+    let mut sys = System::new();
+    sys.refresh_memory();
+    sys.available_memory()
+}
+
+/// Whether the machine is running on battery. False wherever this cannot be determined.
+pub fn on_battery_power() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        let Ok(supplies) = std::fs::read_dir("/sys/class/power_supply") else {
+            return false;
+        };
+        let read = |path: std::path::PathBuf| std::fs::read_to_string(path).unwrap_or_default();
+        supplies.flatten().any(|supply| {
+            read(supply.path().join("type")).trim() == "Battery"
+                && read(supply.path().join("status")).trim() == "Discharging"
+        })
+    }
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("pmset")
+            .args(["-g", "batt"])
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).contains("'Battery Power'"))
+            .unwrap_or(false)
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        false
+    }
+}
+
+/// Hottest CPU temperature reported by the sensors, in degrees Celsius. Falls back to the
+/// hottest sensor of any kind when none is labelled as the CPU.
+pub fn cpu_temperature(components: &mut Components) -> Option<f32> {
+    components.refresh(false);
+    let hottest = |cpu_only: bool| {
+        components
+            .list()
+            .iter()
+            .filter(|component| !cpu_only || is_cpu_sensor(component.label()))
+            .filter_map(|component| component.temperature())
+            .filter(|temp| temp.is_finite())
+            .reduce(f32::max)
+    };
+    hottest(true).or_else(|| hottest(false))
+}
+
+fn is_cpu_sensor(label: &str) -> bool {
+    let label = label.to_lowercase();
+    [
+        "cpu", "core", "package", "tctl", "tdie", "k10temp", "coretemp", "soc",
+    ]
+    .iter()
+    .any(|name| label.contains(name))
+}
+
+/// One-minute load average divided by the number of cores. None where the platform does
+/// not report a load average.
+pub fn load_per_core() -> Option<f32> {
+    if cfg!(target_os = "windows") {
+        return None;
+    }
+    Some(System::load_average().one as f32 / num_cores().max(1) as f32)
+}
+
 /// Memory used by the current process, in GB.
 #[allow(unused)]
 pub fn process_memory_gb() -> f64 {
@@ -6,6 +6,7 @@
 
 use sha3::{Digest, Keccak256};
 use std::fmt::Display;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Task {
@@ -23,6 +24,10 @@ pub struct Task {
 
     /// The type of task (proof required or only hash)
     pub task_type: crate::nexus_orchestrator::TaskType,
+
+    /// When the orchestrator would like the next task to be fetched, from a Retry-After
+    /// header on the response that carried this task
+    pub retry_after: Option<Duration>,
 }
 
 impl Task {
@@ -40,6 +45,7 @@ impl Task {
             public_inputs: public_inputs.clone(),
             public_inputs_list: vec![public_inputs],
             task_type,
+            retry_after: None,
         }
     }
 
@@ -89,6 +95,7 @@ impl From<&crate::nexus_orchestrator::Task> for Task {
             public_inputs: task.public_inputs_list.first().cloned().unwrap_or_default(),
             public_inputs_list: task.public_inputs_list.clone(),
             task_type: crate::nexus_orchestrator::TaskType::try_from(task.task_type).unwrap(),
+            retry_after: None,
         }
     }
 }
@@ -0,0 +1,295 @@
+//! Failed task capture and offline replay
+//!
+//! `--capture-failed <dir>` saves every task whose proving fails, including failed local
+//! verification, as a self-contained `<timestamp>-<task id>.json` capture holding the task,
+//! the error and the prover's last stderr lines. `nexus-network replay <capture>` runs a
+//! capture through the same [`ProvingPipeline`] again without contacting the orchestrator or
+//! analytics, printing every step, so a failure can be reproduced from a bug report.
+
+use crate::atomic_write::write_atomic;
+use crate::consts::cli_consts::PROVER_MAX_SLOTS;
+use crate::environment::Environment;
+use crate::event_bus::{ConsoleSink, EventBus};
+use crate::nexus_orchestrator::TaskType;
+use crate::proof_archive::sanitize;
+use crate::prover::child::ProverPool;
+use crate::prover::engine::FIB_INPUT_INITIAL_ELF;
+use crate::prover::input::InputParser;
+use crate::prover::pipeline::ProvingPipeline;
+use crate::prover::{ProverError, ProverResult};
+use crate::task::Task;
+use crate::thermal::{ThermalConfig, ThermalMonitor};
+use crate::workers::core::EventSender;
+use crate::{print_cmd_info, print_cmd_warn};
+use chrono::Utc;
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use tokio::sync::mpsc;
+
+/// Version of the capture layout, recorded in every capture
+pub const CAPTURE_FORMAT_VERSION: u32 = 1;
+
+/// Command-line options for capturing failed tasks
+#[derive(Args, Debug, Clone)]
+pub struct CaptureArgs {
+    /// Save every task that fails to prove to this directory, for `nexus-network replay`
+    #[arg(long = "capture-failed", value_name = "DIR")]
+    pub capture_failed: Option<PathBuf>,
+}
+
+#[derive(Error, Debug)]
+pub enum CaptureError {
+    #[error("Could not read capture: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Capture is not valid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Capture format version {0} is not supported; expected {CAPTURE_FORMAT_VERSION}")]
+    UnsupportedVersion(u32),
+
+    #[error("Capture is malformed: {0}")]
+    Malformed(String),
+}
+
+/// A failed task and why it failed, written as JSON
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TaskCapture {
+    pub format_version: u32,
+    pub cli_version: String,
+    /// RFC 3339 time the capture was written
+    pub created_at: String,
+    pub task_id: String,
+    pub program_id: String,
+    /// Orchestrator task type, e.g. `PROOF_HASH`
+    pub task_type: String,
+    /// Public inputs as sent by the orchestrator, hex-encoded, one per input
+    pub public_inputs_hex: Vec<String>,
+    /// Keccak-256 of the guest program the task failed with
+    pub elf_keccak256: String,
+    pub error: String,
+    /// The prover subprocess's last stderr lines, if it wrote any
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stderr_tail: Vec<String>,
+}
+
+impl TaskCapture {
+    pub fn new(task: &Task, error: &ProverError) -> Self {
+        Self {
+            format_version: CAPTURE_FORMAT_VERSION,
+            cli_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            task_id: task.task_id.clone(),
+            program_id: task.program_id.clone(),
+            task_type: task.task_type.as_str_name().to_string(),
+            public_inputs_hex: task
+                .all_inputs()
+                .iter()
+                .map(|input| to_hex(input))
+                .collect(),
+            elf_keccak256: elf_keccak256(),
+            error: error.to_string(),
+            stderr_tail: error.stderr_tail().to_vec(),
+        }
+    }
+
+    /// Write the capture into `dir`, returning its path
+    pub fn save(&self, dir: &Path) -> std::io::Result<PathBuf> {
+        let name = format!(
+            "{}-{}.json",
+            Utc::now().format("%Y%m%dT%H%M%S%.3fZ"),
+            sanitize(&self.task_id)
+        );
+        let path = dir.join(name);
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        write_atomic(&path, json.as_bytes())?;
+        Ok(path)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, CaptureError> {
+        let capture: Self = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        if capture.format_version != CAPTURE_FORMAT_VERSION {
+            return Err(CaptureError::UnsupportedVersion(capture.format_version));
+        }
+        Ok(capture)
+    }
+
+    /// The captured task, as it was received from the orchestrator
+    pub fn task(&self) -> Result<Task, CaptureError> {
+        let task_type = TaskType::from_str_name(&self.task_type).ok_or_else(|| {
+            CaptureError::Malformed(format!("unknown task type {}", self.task_type))
+        })?;
+        let inputs = self
+            .public_inputs_hex
+            .iter()
+            .map(|hex| from_hex(hex))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Task {
+            task_id: self.task_id.clone(),
+            program_id: self.program_id.clone(),
+            public_inputs: inputs.first().cloned().unwrap_or_default(),
+            public_inputs_list: inputs,
+            task_type,
+            retry_after: None,
+        })
+    }
+}
+
+/// Re-prove a captured task offline, with local verification, printing every step
+pub async fn replay(capture: &TaskCapture) -> Result<ProverResult, Box<dyn std::error::Error>> {
+    let task = capture.task()?;
+    print_cmd_info!(
+        "Replaying task",
+        "{} ({}, {}) captured by version {} at {}",
+        task.task_id,
+        task.program_id,
+        capture.task_type,
+        capture.cli_version,
+        capture.created_at
+    );
+    for (index, input) in task.all_inputs().iter().enumerate() {
+        match InputParser::parse_triple_input(input) {
+            Ok(inputs) => print_cmd_info!("Input", "{}: {:?}", index, inputs),
+            Err(e) => print_cmd_warn!("Input", "{}: {} ({})", index, to_hex(input), e),
+        }
+    }
+    print_cmd_info!("Original error", "{}", capture.error);
+    for line in &capture.stderr_tail {
+        print_cmd_info!("Original stderr", "{}", line);
+    }
+    if capture.cli_version != env!("CARGO_PKG_VERSION") {
+        print_cmd_warn!(
+            "Version differs",
+            "Captured with {}, replaying with {}",
+            capture.cli_version,
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+    if capture.elf_keccak256 != elf_keccak256() {
+        print_cmd_warn!(
+            "Guest program differs",
+            "The task failed with a different guest program than this version proves with"
+        );
+    }
+
+    // Print every event the pipeline sends, as headless mode does
+    let (sender, receiver) = mpsc::channel(crate::consts::cli_consts::EVENT_QUEUE_SIZE);
+    let mut events = EventBus::new(receiver);
+    events.add_sink(Box::new(ConsoleSink::plain()));
+    events.start();
+
+    let event_sender = EventSender::new(sender);
+    // Without --max-threads to bound it, memory alone sizes the pool
+    let mut provers =
+        ProverPool::for_available_memory(PROVER_MAX_SLOTS, Some((event_sender.clone(), 0)));
+    let mut thermal = ThermalMonitor::new(ThermalConfig::default());
+    // A custom environment without an orchestrator keeps analytics off
+    let offline = Environment::Custom {
+        orchestrator_url: String::new(),
+    };
+    let result = ProvingPipeline::prove_authenticated(
+        &mut provers,
+        &mut thermal,
+        &task,
+        &offline,
+        "replay",
+        &event_sender,
+        true,
+    )
+    .await;
+    drop((provers, event_sender));
+    events.shutdown().await;
+
+    let (proofs, combined_hash, individual_proof_hashes) = result.inspect_err(|e| {
+        for line in e.stderr_tail() {
+            print_cmd_info!("Prover stderr", "{}", line);
+        }
+    })?;
+    Ok(ProverResult {
+        proofs,
+        combined_hash,
+        individual_proof_hashes,
+    })
+}
+
+fn elf_keccak256() -> String {
+    format!("{:x}", Keccak256::digest(FIB_INPUT_INITIAL_ELF))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(hex: &str) -> Result<Vec<u8>, CaptureError> {
+    let malformed = || CaptureError::Malformed(format!("invalid hex input {}", hex));
+    if hex.len() % 2 != 0 {
+        return Err(malformed());
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .ok_or_else(malformed)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_capture_round_trips_the_task() {
+        let mut task = Task::new(
+            "task/1".to_string(),
+            "fib_input_initial".to_string(),
+            vec![1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0],
+            TaskType::AllProofHashes,
+        );
+        task.public_inputs_list.push(vec![0xff, 0x10]);
+        let error = ProverError::Stwo("Proof verification failed".to_string());
+
+        let dir = tempdir().unwrap();
+        let path = TaskCapture::new(&task, &error).save(dir.path()).unwrap();
+        assert!(
+            path.file_name()
+                .unwrap()
+                .to_string_lossy()
+                .ends_with("-task_1.json")
+        );
+
+        let capture = TaskCapture::load(&path).unwrap();
+        assert_eq!(capture.public_inputs_hex[1], "ff10");
+        assert!(capture.error.contains("Proof verification failed"));
+        assert_eq!(capture.task().unwrap(), task);
+    }
+
+    #[test]
+    fn test_malformed_captures_are_rejected() {
+        let task = Task::new(
+            "task".to_string(),
+            "fib_input_initial".to_string(),
+            vec![],
+            TaskType::ProofRequired,
+        );
+        let mut capture = TaskCapture::new(&task, &ProverError::MalformedTask("x".to_string()));
+        capture.public_inputs_hex = vec!["abc".to_string()];
+        assert!(matches!(capture.task(), Err(CaptureError::Malformed(_))));
+        capture.public_inputs_hex = vec!["zz".to_string()];
+        assert!(matches!(capture.task(), Err(CaptureError::Malformed(_))));
+
+        capture.format_version = CAPTURE_FORMAT_VERSION + 1;
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("capture.json");
+        std::fs::write(&path, serde_json::to_string(&capture).unwrap()).unwrap();
+        assert!(matches!(
+            TaskCapture::load(&path),
+            Err(CaptureError::UnsupportedVersion(_))
+        ));
+    }
+}
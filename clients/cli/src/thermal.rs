@@ -0,0 +1,331 @@
+//! Thermal back-off
+//!
+//! Long proving runs can overheat small, fanless machines. The [`ThermalMonitor`] watches
+//! the CPU temperature and the load average and eases off as either approaches its limit:
+//! close to a limit inputs are proven one at a time, and above it no new inputs are started
+//! until the machine has cooled down. Between tasks a hot machine pauses for a cool-down
+//! period before the next task is fetched. Every change of pace is reported as an event.
+
+use crate::consts::cli_consts::{
+    THERMAL_COOLDOWN_SECS, THERMAL_LOAD_MARGIN, THERMAL_TEMP_MARGIN_C,
+};
+use crate::events::{Event, EventType, ProverState};
+use crate::logging::LogLevel;
+use crate::system;
+use crate::workers::core::EventSender;
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+use sysinfo::Components;
+
+/// Temperature and load limits, from the `thermal` section of the config file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ThermalConfig {
+    /// Back off once the CPU reaches this temperature, in degrees Celsius
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cpu_temp_c: Option<f32>,
+    /// Back off once the one-minute load average per core reaches this value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_load_per_core: Option<f32>,
+    /// Length of a cool-down pause between tasks, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cooldown_secs: Option<u64>,
+}
+
+impl ThermalConfig {
+    /// Whether no limit is set, so proving never backs off
+    pub fn is_unrestricted(&self) -> bool {
+        self.max_cpu_temp_c.is_none() && self.max_load_per_core.is_none()
+    }
+
+    pub fn cooldown(&self) -> Duration {
+        Duration::from_secs(self.cooldown_secs.unwrap_or(THERMAL_COOLDOWN_SECS))
+    }
+
+    /// How hard the machine may be worked given a sensor reading
+    pub fn assess(&self, reading: &ThermalReading) -> Heat {
+        let temp = self
+            .max_cpu_temp_c
+            .zip(reading.cpu_temp_c)
+            .map(|(max, temp)| (temp, max, max - THERMAL_TEMP_MARGIN_C));
+        let load = self
+            .max_load_per_core
+            .zip(reading.load_per_core)
+            .map(|(max, load)| (load, max, max - THERMAL_LOAD_MARGIN));
+
+        if let Some((celsius, limit, _)) = temp.filter(|(temp, max, _)| temp >= max) {
+            return Heat::Hot(Overheat::Temperature { celsius, limit });
+        }
+        if let Some((load, limit, _)) = load.filter(|(load, max, _)| load >= max) {
+            return Heat::Hot(Overheat::Load { load, limit });
+        }
+        let warm = temp.is_some_and(|(temp, _, warm)| temp >= warm)
+            || load.is_some_and(|(load, _, warm)| load >= warm);
+        if warm { Heat::Warm } else { Heat::Cool }
+    }
+}
+
+/// Command-line options for thermal back-off. They replace the corresponding settings from
+/// the config file.
+#[derive(Args, Debug, Clone, Default)]
+pub struct ThermalArgs {
+    /// Back off once the CPU reaches this temperature, in degrees Celsius
+    #[arg(long = "max-cpu-temp", value_name = "CELSIUS")]
+    pub max_cpu_temp: Option<f32>,
+
+    /// Back off once the one-minute load average per core reaches this value
+    #[arg(long = "max-load", value_name = "LOAD")]
+    pub max_load: Option<f32>,
+
+    /// Length of a cool-down pause between tasks while the machine is too hot, in seconds
+    #[arg(long = "cooldown", value_name = "SECS")]
+    pub cooldown: Option<u64>,
+}
+
+impl ThermalArgs {
+    pub fn apply(&self, mut config: ThermalConfig) -> ThermalConfig {
+        if self.max_cpu_temp.is_some() {
+            config.max_cpu_temp_c = self.max_cpu_temp;
+        }
+        if self.max_load.is_some() {
+            config.max_load_per_core = self.max_load;
+        }
+        if self.cooldown.is_some() {
+            config.cooldown_secs = self.cooldown;
+        }
+        config
+    }
+}
+
+/// Sensor values the monitor acts on. Either may be missing on machines that lack them.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ThermalReading {
+    pub cpu_temp_c: Option<f32>,
+    pub load_per_core: Option<f32>,
+}
+
+/// How hard the machine may currently be worked
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Heat {
+    /// Well within the limits
+    Cool,
+    /// Close to a limit: prove one input at a time
+    Warm,
+    /// At or above a limit: start nothing new
+    Hot(Overheat),
+}
+
+/// The limit a hot machine has reached
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Overheat {
+    Temperature { celsius: f32, limit: f32 },
+    Load { load: f32, limit: f32 },
+}
+
+impl fmt::Display for Overheat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Overheat::Temperature { celsius, limit } => {
+                write!(f, "CPU at {:.0}°C, limit {:.0}°C", celsius, limit)
+            }
+            Overheat::Load { load, limit } => {
+                write!(f, "load {:.2} per core, limit {:.2}", load, limit)
+            }
+        }
+    }
+}
+
+/// Watches the host sensors and decides how many inputs may be proven at once
+pub struct ThermalMonitor {
+    config: ThermalConfig,
+    components: Components,
+    /// Whether the last assessment was worse than cool, to report the way back
+    backing_off: bool,
+    last: Option<Heat>,
+}
+
+impl ThermalMonitor {
+    pub fn new(config: ThermalConfig) -> Self {
+        let components = if config.max_cpu_temp_c.is_some() {
+            Components::new_with_refreshed_list()
+        } else {
+            Components::new()
+        };
+        Self {
+            config,
+            components,
+            backing_off: false,
+            last: None,
+        }
+    }
+
+    fn read(&mut self) -> ThermalReading {
+        ThermalReading {
+            cpu_temp_c: self
+                .config
+                .max_cpu_temp_c
+                .and_then(|_| system::cpu_temperature(&mut self.components)),
+            load_per_core: self
+                .config
+                .max_load_per_core
+                .and_then(|_| system::load_per_core()),
+        }
+    }
+
+    /// Number of inputs that may be in flight, out of `slots`. Zero means none should be
+    /// started until the machine has cooled down.
+    pub async fn allowed_parallelism(&mut self, slots: usize, events: &EventSender) -> usize {
+        if self.config.is_unrestricted() {
+            return slots;
+        }
+        let reading = self.read();
+        let heat = self.config.assess(&reading);
+        self.report(heat, events).await;
+        match heat {
+            Heat::Cool => slots,
+            Heat::Warm => 1,
+            Heat::Hot(_) => 0,
+        }
+    }
+
+    /// Interval at which a hot machine is checked again while nothing is being proven
+    pub fn poll_interval(&self) -> Duration {
+        self.config.cooldown()
+    }
+
+    /// Pause between tasks until the machine is no longer hot, reporting the pause as a
+    /// state change
+    pub async fn cool_down(&mut self, events: &EventSender) {
+        if self.config.is_unrestricted() {
+            return;
+        }
+        let mut paused = false;
+        loop {
+            let reading = self.read();
+            let heat = self.config.assess(&reading);
+            let Heat::Hot(overheat) = heat else {
+                if !paused {
+                    self.report(heat, events).await;
+                    return;
+                }
+                events
+                    .send_event(Event::state_change(
+                        ProverState::Waiting,
+                        "Resuming: the machine has cooled down".to_string(),
+                    ))
+                    .await;
+                self.last = Some(heat);
+                self.backing_off = heat != Heat::Cool;
+                return;
+            };
+            if !paused {
+                events
+                    .send_event(Event::state_change(
+                        ProverState::Paused,
+                        format!("Paused to cool down: {}", overheat),
+                    ))
+                    .await;
+                paused = true;
+            }
+            self.last = Some(heat);
+            self.backing_off = true;
+            tokio::time::sleep(self.config.cooldown()).await;
+        }
+    }
+
+    /// Explain a change of pace
+    async fn report(&mut self, heat: Heat, events: &EventSender) {
+        let changed = match (self.last, heat) {
+            (Some(Heat::Hot(_)), Heat::Hot(_)) => false,
+            (Some(last), heat) => last != heat,
+            (None, _) => true,
+        };
+        self.last = Some(heat);
+        if !changed {
+            return;
+        }
+        let msg = match heat {
+            Heat::Hot(overheat) => format!("Thermal: {}; not starting new inputs", overheat),
+            Heat::Warm => "Thermal: close to the limit; proving one input at a time".to_string(),
+            Heat::Cool if self.backing_off => "Thermal: back within limits".to_string(),
+            Heat::Cool => return,
+        };
+        self.backing_off = heat != Heat::Cool;
+        let level = if heat == Heat::Cool {
+            LogLevel::Info
+        } else {
+            LogLevel::Warn
+        };
+        events
+            .send_prover_event(0, msg, EventType::Refresh, level)
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ThermalConfig {
+        ThermalConfig {
+            max_cpu_temp_c: Some(85.0),
+            max_load_per_core: Some(1.5),
+            cooldown_secs: None,
+        }
+    }
+
+    fn reading(temp: Option<f32>, load: Option<f32>) -> ThermalReading {
+        ThermalReading {
+            cpu_temp_c: temp,
+            load_per_core: load,
+        }
+    }
+
+    #[test]
+    fn test_assess_backs_off_near_and_above_limits() {
+        let config = config();
+        assert_eq!(config.assess(&reading(Some(60.0), Some(0.5))), Heat::Cool);
+        assert_eq!(config.assess(&reading(Some(82.0), Some(0.5))), Heat::Warm);
+        assert_eq!(config.assess(&reading(Some(60.0), Some(1.3))), Heat::Warm);
+        assert_eq!(
+            config.assess(&reading(Some(90.0), Some(2.0))),
+            Heat::Hot(Overheat::Temperature {
+                celsius: 90.0,
+                limit: 85.0
+            })
+        );
+        assert_eq!(
+            config.assess(&reading(None, Some(2.0))),
+            Heat::Hot(Overheat::Load {
+                load: 2.0,
+                limit: 1.5
+            })
+        );
+        // Missing sensors never hold proving back
+        assert_eq!(config.assess(&reading(None, None)), Heat::Cool);
+        assert_eq!(
+            ThermalConfig::default().assess(&reading(Some(120.0), Some(9.0))),
+            Heat::Cool
+        );
+    }
+
+    #[test]
+    fn test_args_override_config() {
+        let args = ThermalArgs {
+            max_cpu_temp: Some(75.0),
+            max_load: None,
+            cooldown: Some(30),
+        };
+        let config = args.apply(config());
+        assert_eq!(config.max_cpu_temp_c, Some(75.0));
+        assert_eq!(config.max_load_per_core, Some(1.5));
+        assert_eq!(config.cooldown(), Duration::from_secs(30));
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ThermalConfig>(&json).unwrap(),
+            config
+        );
+    }
+}
@@ -4,6 +4,7 @@
 
 use crate::environment::Environment;
 use crate::events::Event as WorkerEvent;
+use crate::nexus_orchestrator::TaskDifficulty;
 use crate::ui::dashboard::{DashboardState, render_dashboard};
 use crate::ui::login::render_login;
 use crate::ui::splash::render_splash;
@@ -20,6 +21,7 @@ pub struct UIConfig {
     pub update_available: bool,
     pub latest_version: Option<String>,
     pub gflops: f64,
+    pub max_difficulty: TaskDifficulty,
 }
 
 impl UIConfig {
@@ -29,6 +31,7 @@ impl UIConfig {
         update_available: bool,
         latest_version: Option<String>,
         gflops: f64,
+        max_difficulty: TaskDifficulty,
     ) -> Self {
         Self {
             with_background_color,
@@ -36,6 +39,7 @@ impl UIConfig {
             update_available,
             latest_version,
             gflops,
+            max_difficulty,
         }
     }
 }
@@ -90,6 +94,9 @@ pub struct App {
 
     /// Estimated GFLOP/s
     gflops: f64,
+
+    /// Maximum difficulty of tasks requested from the orchestrator
+    max_difficulty: TaskDifficulty,
 }
 
 impl App {
@@ -115,6 +122,7 @@ impl App {
             version_update_available: ui_config.update_available,
             latest_version: ui_config.latest_version,
             gflops: ui_config.gflops,
+            max_difficulty: ui_config.max_difficulty,
         }
     }
 
@@ -128,6 +136,7 @@ impl App {
             self.version_update_available,
             self.latest_version.clone(),
             self.gflops,
+            self.max_difficulty,
         );
         let state = DashboardState::new(
             node_id,
@@ -181,6 +190,7 @@ pub async fn run<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> std::i
                     app.version_update_available,
                     app.latest_version.clone(),
                     app.gflops,
+                    app.max_difficulty,
                 );
                 app.current_screen = Screen::Dashboard(Box::new(DashboardState::new(
                     app.node_id,
@@ -217,6 +227,7 @@ pub async fn run<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> std::i
                                 app.version_update_available,
                                 app.latest_version.clone(),
                                 app.gflops,
+                                app.max_difficulty,
                             );
                             app.current_screen = Screen::Dashboard(Box::new(DashboardState::new(
                                 app.node_id,
@@ -32,6 +32,7 @@ pub fn render_header(f: &mut Frame, area: ratatui::layout::Rect, state: &Dashboa
             "STATUS: AWAITING TASK",
             Style::default().fg(theme::ACCENT_BLUE),
         ),
+        ProverState::Paused => ("STATUS: PAUSED", Style::default().fg(theme::COLOR_WARNING)),
     };
 
     let status = Paragraph::new(status_text)
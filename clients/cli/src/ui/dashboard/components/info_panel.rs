@@ -50,6 +50,10 @@ pub fn render_info_panel(f: &mut Frame, area: ratatui::layout::Rect, state: &Das
             Cell::from("Threads"),
             Cell::from(state.num_threads.to_string()).style(theme::text_style()),
         ]),
+        Row::new(vec![
+            Cell::from("Next task"),
+            Cell::from(state.task_fetch_info.describe()).style(theme::text_style()),
+        ]),
     ];
 
     let table = Table::new(rows, vec![Constraint::Length(10), Constraint::Min(0)])
@@ -1,8 +1,9 @@
 //! Dashboard logs panel component
 
 use super::super::state::DashboardState;
-use super::super::utils::{clean_http_error_message, format_compact_timestamp};
+use super::super::utils::format_compact_timestamp;
 use super::theme;
+use crate::consts::cli_consts::SUBPROCESS_STDERR_DISPLAY_LINES;
 use crate::events::EventType;
 use ratatui::Frame;
 use ratatui::prelude::Style;
@@ -19,8 +20,7 @@ pub fn render_logs_panel(f: &mut Frame, area: ratatui::layout::Rect, state: &Das
         .iter()
         .filter(|event| event.should_display())
         .rev()
-        .take(log_count)
-        .map(|event| {
+        .flat_map(|event| {
             let (status_icon, msg_style) = match event.event_type {
                 EventType::Success => ("✔", Style::default().fg(theme::COLOR_SUCCESS)),
                 EventType::Error => ("✖", Style::default().fg(theme::COLOR_ERROR)),
@@ -29,14 +29,27 @@ pub fn render_logs_panel(f: &mut Frame, area: ratatui::layout::Rect, state: &Das
             };
 
             let compact_time = format_compact_timestamp(&event.timestamp);
-            let cleaned_msg = clean_http_error_message(&event.msg);
 
-            Line::from(vec![
+            let mut lines = vec![Line::from(vec![
                 Span::styled(format!("{} ", compact_time), theme::dim_text_style()),
                 Span::styled(format!("{} ", status_icon), msg_style),
-                Span::styled(cleaned_msg, msg_style),
-            ])
+                Span::styled(event.msg.clone(), msg_style),
+            ])];
+
+            // Show the prover's last words under a failed task
+            if let Some(failure) = &event.task_failure {
+                let tail = &failure.stderr_tail;
+                let shown = &tail[tail.len().saturating_sub(SUBPROCESS_STDERR_DISPLAY_LINES)..];
+                lines.extend(shown.iter().map(|line| {
+                    Line::from(Span::styled(
+                        format!("            │ {}", line),
+                        theme::dim_text_style(),
+                    ))
+                }));
+            }
+            lines
         })
+        .take(log_count)
         .collect();
 
     let logs_block = Block::default()
@@ -4,13 +4,38 @@
 
 use super::super::state::DashboardState;
 use super::theme;
+use crate::events::format_duration;
+use crate::network::CircuitState;
+use crate::nexus_orchestrator::TaskDifficulty;
 use ratatui::Frame;
 use ratatui::prelude::{Alignment, Constraint, Direction, Layout, Modifier, Style};
 use ratatui::symbols;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Axis, Block, BorderType, Borders, Chart, Dataset, Gauge, Paragraph};
 
-/// Renders the system metric charts (CPU and RAM).
+/// Display label for the requested task difficulty.
+fn difficulty_label(difficulty: TaskDifficulty) -> &'static str {
+    match difficulty {
+        TaskDifficulty::Small => "Small",
+        TaskDifficulty::Medium => "Medium",
+        TaskDifficulty::Large => "Large",
+    }
+}
+
+/// Orchestrator availability, as seen by the circuit breaker.
+fn circuit_span(state: CircuitState) -> Span<'static> {
+    let (label, color) = match state {
+        CircuitState::Closed => ("Online", theme::COLOR_SUCCESS),
+        CircuitState::HalfOpen => ("Probing", theme::COLOR_WARNING),
+        CircuitState::Open => ("Paused (unreachable)", theme::COLOR_ERROR),
+    };
+    Span::styled(
+        label,
+        Style::default().fg(color).add_modifier(Modifier::BOLD),
+    )
+}
+
+/// Renders the system metric charts (CPU with temperature, and RAM).
 pub fn render_system_charts(f: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
     let metrics = &state.system_metrics;
 
@@ -27,27 +52,54 @@ pub fn render_system_charts(f: &mut Frame, area: ratatui::layout::Rect, state: &
         .map(|(i, &v)| (i as f64, v as f64))
         .collect();
 
-    let cpu_datasets = vec![
+    let temp_data: Vec<(f64, f64)> = state
+        .temp_history
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| (i as f64, v as f64))
+        .collect();
+
+    let mut cpu_datasets = vec![
         Dataset::default()
             .name("CPU %")
             .marker(symbols::Marker::Braille)
             .style(Style::default().fg(metrics.cpu_color()))
             .data(&cpu_data),
     ];
+    if !temp_data.is_empty() {
+        cpu_datasets.push(
+            Dataset::default()
+                .name("Temp °C")
+                .marker(symbols::Marker::Dot)
+                .style(Style::default().fg(metrics.temp_color()))
+                .data(&temp_data),
+        );
+    }
+
+    let mut cpu_title = vec![
+        Span::raw("CPU Usage ("),
+        Span::styled(
+            format!("{:.1}%", metrics.cpu_percent),
+            Style::default()
+                .fg(metrics.cpu_color())
+                .add_modifier(Modifier::BOLD),
+        ),
+    ];
+    if let Some(temp) = metrics.format_temp() {
+        cpu_title.push(Span::raw(" · "));
+        cpu_title.push(Span::styled(
+            temp,
+            Style::default()
+                .fg(metrics.temp_color())
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    cpu_title.push(Span::raw(")"));
 
     let cpu_chart = Chart::new(cpu_datasets)
         .block(
             Block::default()
-                .title(Line::from(vec![
-                    Span::raw("CPU Usage ("),
-                    Span::styled(
-                        format!("{:.1}%", metrics.cpu_percent),
-                        Style::default()
-                            .fg(metrics.cpu_color())
-                            .add_modifier(Modifier::BOLD),
-                    ),
-                    Span::raw(")"),
-                ]))
+                .title(Line::from(cpu_title))
                 .title_alignment(Alignment::Center)
                 .borders(Borders::ALL)
                 .border_style(theme::border_style())
@@ -130,10 +182,11 @@ pub fn render_zkvm_metrics(f: &mut Frame, area: ratatui::layout::Rect, state: &D
         .direction(Direction::Vertical)
         .margin(1)
         .constraints([
-            Constraint::Length(4),
+            Constraint::Length(5),
             Constraint::Length(1),
             Constraint::Length(3),
             Constraint::Length(3),
+            Constraint::Length(3),
         ])
         .split(zkvm_block.inner(area));
 
@@ -157,12 +210,16 @@ pub fn render_zkvm_metrics(f: &mut Frame, area: ratatui::layout::Rect, state: &D
         Line::from(vec![
             Span::styled("Difficulty Req: ", theme::dim_text_style()),
             Span::styled(
-                "Large",
+                difficulty_label(state.max_difficulty),
                 Style::default()
                     .fg(theme::ACCENT_BLUE)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
+        Line::from(vec![
+            Span::styled("Orchestrator:   ", theme::dim_text_style()),
+            circuit_span(state.circuit_state),
+        ]),
         Line::from(vec![
             Span::styled("Performance:    ", theme::dim_text_style()),
             Span::styled(
@@ -202,4 +259,27 @@ pub fn render_zkvm_metrics(f: &mut Frame, area: ratatui::layout::Rect, state: &D
         .percent(metrics.success_rate() as u16)
         .label(format!("{:.1}%", metrics.success_rate()));
     f.render_widget(success_gauge, inner_chunks[3]);
+
+    let (progress_ratio, progress_label) = match &state.proving_progress {
+        Some(progress) => (
+            progress.ratio(),
+            format!(
+                "{} / {} · ETA {}",
+                progress.completed,
+                progress.total,
+                format_duration(progress.eta())
+            ),
+        ),
+        None => (0.0, "Idle".to_string()),
+    };
+    let progress_gauge = Gauge::default()
+        .block(Block::default().title("Task Progress"))
+        .gauge_style(
+            Style::default()
+                .fg(theme::ACCENT_BLUE)
+                .bg(theme::PRIMARY_BLACK),
+        )
+        .ratio(progress_ratio)
+        .label(progress_label);
+    f.render_widget(progress_gauge, inner_chunks[4]);
 }
@@ -49,7 +49,7 @@ pub fn render_dashboard(f: &mut Frame, state: &DashboardState) {
     // --- Left Column: System Info & Metrics ---
     let left_chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(8), Constraint::Min(0)])
+        .constraints([Constraint::Length(10), Constraint::Min(0)])
         .split(content_chunks[0]);
     info_panel::render_info_panel(f, left_chunks[0], state);
     metrics::render_system_charts(f, left_chunks[1], state);
@@ -4,13 +4,15 @@
 
 use crate::consts::cli_consts::MAX_ACTIVITY_LOGS;
 use crate::environment::Environment;
-use crate::events::{Event as WorkerEvent, ProverState};
+use crate::events::{Event as WorkerEvent, ProverState, ProvingProgress};
+use crate::network::CircuitState;
+use crate::nexus_orchestrator::TaskDifficulty;
 use crate::ui::app::UIConfig;
 use crate::ui::metrics::{SystemMetrics, TaskFetchInfo, ZkVMMetrics};
 
 use std::collections::VecDeque;
 use std::time::Instant;
-use sysinfo::System;
+use sysinfo::{Components, System};
 
 /// State for tracking fetching operations
 #[derive(Debug, Clone)]
@@ -38,11 +40,22 @@ pub struct DashboardState {
     pub tick: usize,
     pub cpu_history: Vec<u64>, // Field for CPU chart data
     pub ram_history: Vec<u64>, // Field for RAM chart data
+    /// CPU temperature chart data; empty where the machine reports no temperature
+    pub temp_history: Vec<u64>,
     fetching_state: FetchingState,
     sysinfo: System,
+    components: Components,
     current_prover_state: ProverState,
     pub step2_start_time: Option<Instant>,
     pub waiting_start_info: Option<(Instant, u64)>,
+    /// Why the node is waiting to fetch its next task
+    pub wait_reason: Option<String>,
+    /// Per-input progress of the task currently being proven
+    pub proving_progress: Option<ProvingProgress>,
+    /// Maximum difficulty of tasks requested from the orchestrator
+    pub max_difficulty: TaskDifficulty,
+    /// Orchestrator circuit breaker state, as last reported by a worker
+    pub circuit_state: CircuitState,
 }
 
 impl DashboardState {
@@ -74,11 +87,17 @@ impl DashboardState {
             tick: 0,
             cpu_history: vec![0; 60], // Initialize with 60 zero-values
             ram_history: vec![0; 60], // Initialize with 60 zero-values
+            temp_history: Vec::new(),
             fetching_state: FetchingState::Idle,
             sysinfo: System::new_all(),
+            components: Components::new_with_refreshed_list(),
             current_prover_state: ProverState::Waiting,
             step2_start_time: None,
             waiting_start_info: None,
+            wait_reason: None,
+            proving_progress: None,
+            max_difficulty: ui_config.max_difficulty,
+            circuit_state: CircuitState::Closed,
         }
     }
 
@@ -102,6 +121,10 @@ impl DashboardState {
         &mut self.sysinfo
     }
 
+    pub fn get_components_mut(&mut self) -> &mut Components {
+        &mut self.components
+    }
+
     pub fn add_to_activity_log(&mut self, event: WorkerEvent) {
         if self.activity_logs.len() >= MAX_ACTIVITY_LOGS {
             self.activity_logs.pop_front();
@@ -3,7 +3,7 @@
 //! Contains all methods for updating dashboard state from events
 
 use super::state::{DashboardState, FetchingState};
-use crate::events::{Event as WorkerEvent, EventType, Worker};
+use crate::events::{Event as WorkerEvent, EventType, ProverState, Worker};
 use crate::system;
 use crate::ui::metrics::{SystemMetrics, TaskFetchInfo};
 
@@ -25,6 +25,7 @@ impl DashboardState {
 
         // Update GFLOPs in real-time
         self.system_metrics.gflops = system::measure_gflops_realtime() as f64;
+        self.system_metrics.cpu_temp_c = system::cpu_temperature(self.get_components_mut());
 
         // --- FIX: Add logic to update the history for the charts ---
         self.cpu_history.remove(0);
@@ -33,6 +34,13 @@ impl DashboardState {
         self.ram_history.remove(0);
         self.ram_history
             .push((self.system_metrics.ram_ratio() * 100.0) as u64);
+        if let Some(temp) = self.system_metrics.cpu_temp_c {
+            if self.temp_history.is_empty() {
+                self.temp_history = vec![0; 60];
+            }
+            self.temp_history.remove(0);
+            self.temp_history.push(temp as u64);
+        }
         // --- END OF FIX ---
 
         // Process all queued events one by one
@@ -59,10 +67,18 @@ impl DashboardState {
             Worker::ProofSubmitter => self.handle_proof_submitter_event(event),
         }
 
+        // Circuit breaker changes can come from either network worker
+        if let Some(state) = event.circuit_state {
+            self.circuit_state = state;
+        }
+
         // Handle state changes regardless of worker
         if event.event_type == EventType::StateChange {
             if let Some(state) = event.prover_state {
                 self.set_current_prover_state(state);
+                if state != ProverState::Proving {
+                    self.proving_progress = None;
+                }
             }
         }
     }
@@ -79,6 +95,7 @@ impl DashboardState {
 
                 // Count this as a task fetch if we haven't seen this task before
                 self.zkvm_metrics.tasks_fetched += 1;
+                self.waiting_start_info = None;
                 // Track Step 2 start (proving begins at the end of Step 1)
                 self.step2_start_time = Some(Instant::now());
             }
@@ -106,12 +123,17 @@ impl DashboardState {
                 if !is_same_message {
                     self.waiting_start_info = Some((Instant::now(), seconds));
                 }
+                self.wait_reason = Self::extract_wait_reason(&event.msg);
             }
         }
     }
 
     /// Handle Prover events
     fn handle_prover_event(&mut self, event: &WorkerEvent) {
+        if let Some(progress) = &event.proving_progress {
+            self.proving_progress = Some(progress.clone());
+        }
+
         if matches!(event.event_type, EventType::Success) {
             // Track Step 3 completion (proof generated)
             if event.msg.contains("Step 3 of 4: Proof generated for task") {
@@ -120,10 +142,12 @@ impl DashboardState {
                     self.zkvm_metrics.last_task_status = "Proved".to_string();
                     self.step2_start_time = None;
                 }
+                self.proving_progress = None;
             }
         } else if matches!(event.event_type, EventType::Error) {
             self.zkvm_metrics.last_task_status = "Proof Failed".to_string();
             self.step2_start_time = None; // Clear timing for failed proof
+            self.proving_progress = None;
         }
     }
 
@@ -153,12 +177,14 @@ impl DashboardState {
 
     /// Update task fetch countdown based on current waiting state
     fn update_task_fetch_countdown(&mut self) {
-        if let Some((_start_time, _original_secs)) = &self.waiting_start_info {
-            self.task_fetch_info = TaskFetchInfo {};
-        } else {
+        self.task_fetch_info = match &self.waiting_start_info {
+            Some((start_time, original_secs)) => TaskFetchInfo {
+                remaining_secs: Some(original_secs.saturating_sub(start_time.elapsed().as_secs())),
+                wait_reason: self.wait_reason.clone(),
+            },
             // No active countdown, assume we can fetch
-            self.task_fetch_info = TaskFetchInfo {};
-        }
+            None => TaskFetchInfo::default(),
+        };
     }
 
     /// Check for fetching timeout (doesn't need events)
@@ -197,6 +223,12 @@ impl DashboardState {
         msg[start + 1..start + end].parse().ok()
     }
 
+    /// Extract why the node waits. Expected format: "...ready for next task (30) seconds - REASON"
+    fn extract_wait_reason(msg: &str) -> Option<String> {
+        let (_, reason) = msg.split_once(") seconds - ")?;
+        Some(reason.trim().to_string()).filter(|reason| !reason.is_empty())
+    }
+
     /// Check if event indicates task completion or error (not Step 1)
     fn is_completion_event(event: &WorkerEvent) -> bool {
         matches!(event.worker, Worker::TaskFetcher)
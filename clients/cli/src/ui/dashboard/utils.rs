@@ -20,19 +20,3 @@ pub fn format_compact_timestamp(timestamp: &str) -> String {
     // Fallback to original timestamp if parsing fails
     timestamp.to_string()
 }
-
-/// Clean HTTP error messages
-pub fn clean_http_error_message(msg: &str) -> String {
-    // Replace verbose HTTP error patterns with cleaner messages
-    if msg.contains("reqwest::Error") && msg.contains("ConnectTimeout") {
-        return "Connection timeout - retrying...".to_string();
-    }
-    if msg.contains("reqwest::Error") && msg.contains("TimedOut") {
-        return "Request timed out - retrying...".to_string();
-    }
-    if msg.contains("reqwest::Error") {
-        return "Network error - retrying...".to_string();
-    }
-    // Return original message if no HTTP error pattern detected
-    msg.to_string()
-}
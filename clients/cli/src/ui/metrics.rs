@@ -14,6 +14,8 @@ pub struct SystemMetrics {
     pub peak_ram_bytes: u64,
     /// Total system RAM in bytes.
     pub total_ram_bytes: u64,
+    /// Hottest CPU temperature in degrees Celsius, where the machine reports one.
+    pub cpu_temp_c: Option<f32>,
     /// Estimated GFLOP/s based on CPU and thread count.
     pub gflops: f64,
     /// Last time CPU was updated for proper refresh timing
@@ -31,6 +33,7 @@ impl Default for SystemMetrics {
                 sys.refresh_memory();
                 sys.total_memory()
             },
+            cpu_temp_c: None,
             gflops: 0.0, // Initialize gflops
             last_cpu_update: None,
         }
@@ -98,6 +101,7 @@ impl SystemMetrics {
             ram_bytes: ram_total,
             peak_ram_bytes: previous_peak.max(ram_total),
             total_ram_bytes: sysinfo.total_memory(),
+            cpu_temp_c: previous_metrics.and_then(|m| m.cpu_temp_c),
             gflops: previous_metrics.map_or(0.0, |m| m.gflops),
             last_cpu_update,
         }
@@ -129,6 +133,19 @@ impl SystemMetrics {
         }
     }
 
+    pub fn format_temp(&self) -> Option<String> {
+        self.cpu_temp_c.map(|temp| format!("{:.0}°C", temp))
+    }
+
+    pub fn temp_color(&self) -> ratatui::prelude::Color {
+        use ratatui::prelude::Color;
+        match self.cpu_temp_c.unwrap_or(0.0) {
+            t if t >= 85.0 => Color::Red,
+            t if t >= 70.0 => Color::Yellow,
+            _ => Color::Green,
+        }
+    }
+
     pub fn ram_color(&self) -> ratatui::prelude::Color {
         use ratatui::prelude::Color;
         let ratio = self.ram_ratio();
@@ -192,5 +209,22 @@ impl ZkVMMetrics {
     }
 }
 
-#[derive(Debug, Clone, Default)]
-pub struct TaskFetchInfo {}
+/// When the next task will be fetched, and why the node waits until then
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskFetchInfo {
+    /// Seconds left until the next fetch, if the node is waiting
+    pub remaining_secs: Option<u64>,
+    /// Why the node waits, as reported by the fetcher
+    pub wait_reason: Option<String>,
+}
+
+impl TaskFetchInfo {
+    /// Short description for the dashboard
+    pub fn describe(&self) -> String {
+        match (self.remaining_secs, &self.wait_reason) {
+            (Some(secs), Some(reason)) if secs > 0 => format!("in {}s ({})", secs, reason),
+            (Some(secs), None) if secs > 0 => format!("in {}s", secs),
+            _ => "Now".to_string(),
+        }
+    }
+}
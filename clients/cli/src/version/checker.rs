@@ -83,7 +83,7 @@ use std::time::{Duration, Instant};
 use mockall::{automock, predicate::*};
 
 // GitHub API endpoint for the latest release
-const GITHUB_RELEASES_URL: &str =
+pub(crate) const GITHUB_RELEASES_URL: &str =
     "https://api.github.com/repos/nexus-xyz/nexus-cli/releases/latest";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -93,6 +93,14 @@ pub struct GitHubRelease {
     pub published_at: String,
     pub html_url: String,
     pub prerelease: bool,
+    #[serde(default)]
+    pub assets: Vec<GitHubAsset>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubAsset {
+    pub name: String,
+    pub browser_download_url: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -236,6 +244,7 @@ mod tests {
             published_at: "2024-01-01T00:00:00Z".to_string(),
             html_url: "https://github.com/nexus-xyz/nexus-cli/releases/tag/v0.9.1".to_string(),
             prerelease: false,
+            assets: Vec::new(),
         };
 
         info.update_from_release(release);
@@ -1,19 +1,42 @@
 //! Version management and validation
 
+use super::requirements::{RequirementsOrigin, RequirementsSource};
+use super::updater::{ReleaseChannel, UpdateOutcome, reexec, self_update};
 use super::{ConstraintType, VersionRequirements};
+use crate::consts::cli_consts::run_exit_codes;
+use crate::events::format_duration;
 use std::error::Error;
+use std::path::Path;
 
 /// Validates version requirements before application startup
 ///
-/// This function fetches and checks version requirements against the current CLI version.
+/// This function loads version requirements from `source` and checks them against the
+/// current CLI version. If the requirements cannot be fetched, a recent cached copy from
+/// `cache_path` is used with a warning.
 /// It handles different constraint types appropriately:
-/// - Blocking: Exits the application with error code 1
+/// - Blocking: Self-updates and restarts if `auto_update` is set, otherwise exits the
+///   application with the `VERSION_BLOCKED` exit code
 /// - Warning/Notice: Displays message but allows continuation
-pub async fn validate_version_requirements() -> Result<(), Box<dyn Error>> {
-    let requirements = match VersionRequirements::fetch().await {
-        Ok(requirements) => requirements,
+pub async fn validate_version_requirements(
+    source: &RequirementsSource,
+    cache_path: &Path,
+    auto_update: bool,
+) -> Result<(), Box<dyn Error>> {
+    let requirements = match VersionRequirements::load(source, cache_path).await {
+        Ok((requirements, RequirementsOrigin::Cache { age, fetch_error })) => {
+            eprintln!(
+                "⚠️ Failed to fetch version requirements ({}). Using cached copy from {} ago.",
+                fetch_error,
+                format_duration(age)
+            );
+            requirements
+        }
+        Ok((requirements, _)) => requirements,
         Err(e) if e.to_string().contains("Failed to fetch") => {
             eprintln!("❌ Failed to fetch version requirements: {}", e);
+            eprintln!(
+                "For offline nodes, pass `--version-requirements <file|url|embedded>` to load them from elsewhere."
+            );
             eprintln!(
                 "If this issue persists, please file a bug report at: https://github.com/nexus-xyz/nexus-cli/issues/new"
             );
@@ -31,6 +54,10 @@ pub async fn validate_version_requirements() -> Result<(), Box<dyn Error>> {
     let current_version = env!("CARGO_PKG_VERSION");
     match requirements.check_version_constraints(current_version, None, None) {
         Ok(Some(violation)) => {
+            if auto_update && violation.constraint_type == ConstraintType::Blocking {
+                eprintln!("❌ Version requirement not met: {}", violation.message);
+                update_and_restart(current_version).await;
+            }
             handle_version_violation(&violation.constraint_type, &violation.message);
         }
         Ok(None) => {
@@ -48,12 +75,32 @@ pub async fn validate_version_requirements() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// Installs the latest stable release and re-executes into it with the same arguments.
+/// Only returns if no newer release could be installed.
+async fn update_and_restart(current_version: &str) {
+    eprintln!("Attempting to update to the latest release...");
+    match self_update(current_version, ReleaseChannel::Stable, false).await {
+        Ok(UpdateOutcome::Updated { latest }) => {
+            eprintln!("✅ Updated to {}, restarting...", latest);
+            let e = reexec();
+            eprintln!("❌ Failed to restart after update: {}", e);
+            std::process::exit(1);
+        }
+        Ok(_) => {
+            eprintln!("❌ No newer release is available to update to.");
+        }
+        Err(e) => {
+            eprintln!("❌ Automatic update failed: {}", e);
+        }
+    }
+}
+
 /// Handles different types of version constraint violations
 fn handle_version_violation(constraint_type: &ConstraintType, message: &str) {
     match constraint_type {
         ConstraintType::Blocking => {
             eprintln!("❌ Version requirement not met: {}", message);
-            std::process::exit(1);
+            std::process::exit(run_exit_codes::VERSION_BLOCKED);
         }
         ConstraintType::Warning => {
             eprintln!("{}", message);
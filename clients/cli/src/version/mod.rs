@@ -1,5 +1,6 @@
 pub mod checker;
 pub mod manager;
 pub mod requirements;
+pub mod updater;
 
 pub use requirements::{ConstraintType, VersionRequirements};
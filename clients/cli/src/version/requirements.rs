@@ -1,6 +1,8 @@
 use reqwest::Client;
 use semver::Version;
 use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
@@ -10,6 +12,13 @@ const FALLBACK_CONFIG_URL: &str =
     "https://raw.githubusercontent.com/nexus-xyz/nexus-cli/refs/heads/main/public/version.json";
 const CONFIG_TIMEOUT: Duration = Duration::from_secs(10);
 
+/// Requirements compiled into the binary: the repository's published requirements as they
+/// were when it was built, so blocking constraints still apply to offline nodes
+const EMBEDDED_REQUIREMENTS: &str = include_str!("../../../../public/version.json");
+
+/// How long a cached copy of the remote requirements may stand in for a failed fetch
+pub const CACHE_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
 #[derive(Error, Debug)]
 pub enum VersionRequirementsError {
     #[error("Failed to fetch config: {0}")]
@@ -20,6 +29,63 @@ pub enum VersionRequirementsError {
 
     #[error("Failed to parse version: {0}")]
     VersionParse(#[from] semver::Error),
+
+    #[error("Failed to read requirements file {path}: {error}")]
+    File {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+/// Where version requirements are loaded from
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum RequirementsSource {
+    /// The published remote endpoints, tried in priority order
+    #[default]
+    Remote,
+    /// A custom URL, e.g. an internal mirror
+    Url(String),
+    /// A local JSON file, for pinned or air-gapped deployments
+    File(PathBuf),
+    /// The defaults compiled into this binary
+    Embedded,
+}
+
+impl From<&str> for RequirementsSource {
+    /// Parse `embedded`, an `http(s)://` URL, or otherwise a file path
+    fn from(value: &str) -> Self {
+        let value = value.trim();
+        if value.is_empty() {
+            RequirementsSource::Remote
+        } else if value.eq_ignore_ascii_case("embedded") {
+            RequirementsSource::Embedded
+        } else if value.starts_with("http://") || value.starts_with("https://") {
+            RequirementsSource::Url(value.to_string())
+        } else {
+            RequirementsSource::File(PathBuf::from(value))
+        }
+    }
+}
+
+/// Where a set of loaded requirements actually came from
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequirementsOrigin {
+    /// Freshly fetched over the network
+    Network,
+    /// Read from a local file
+    File,
+    /// Compiled into the binary
+    Embedded,
+    /// Last-known-good cached copy, used because the fetch failed
+    Cache { age: Duration, fetch_error: String },
+}
+
+/// Last-known-good copy of the remote requirements, stored on disk
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedRequirements {
+    /// Unix timestamp (seconds) of the successful fetch
+    fetched_at: u64,
+    requirements: VersionRequirements,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -52,14 +118,86 @@ pub struct VersionCheckResult {
 }
 
 impl VersionRequirements {
-    /// Fetch version requirements from remote config with multiple fallbacks
-    /// Priority: Firebase Hosting -> Cloud Function Cache -> GitHub
-    pub async fn fetch() -> Result<Self, VersionRequirementsError> {
-        let client = Client::builder()
+    /// Load version requirements from the given source.
+    ///
+    /// Successful network fetches are cached at `cache_path`. If a fetch fails, a cached copy
+    /// younger than [`CACHE_TTL`] is used instead.
+    pub async fn load(
+        source: &RequirementsSource,
+        cache_path: &Path,
+    ) -> Result<(Self, RequirementsOrigin), VersionRequirementsError> {
+        let fetched = match source {
+            RequirementsSource::Embedded => {
+                let requirements = serde_json::from_str(EMBEDDED_REQUIREMENTS)?;
+                return Ok((requirements, RequirementsOrigin::Embedded));
+            }
+            RequirementsSource::File(path) => {
+                let contents =
+                    fs::read_to_string(path).map_err(|error| VersionRequirementsError::File {
+                        path: path.clone(),
+                        error,
+                    })?;
+                let requirements = serde_json::from_str(&contents)?;
+                return Ok((requirements, RequirementsOrigin::File));
+            }
+            RequirementsSource::Url(url) => Self::fetch_from_url(&Self::http_client(), url).await,
+            RequirementsSource::Remote => Self::fetch().await,
+        };
+
+        let now = unix_now();
+        match fetched {
+            Ok(requirements) => {
+                // Best effort: failing to cache must not block startup
+                let _ = Self::write_cache(cache_path, &requirements, now);
+                Ok((requirements, RequirementsOrigin::Network))
+            }
+            Err(e) => match Self::read_cache(cache_path, CACHE_TTL, now) {
+                Some((requirements, age)) => Ok((
+                    requirements,
+                    RequirementsOrigin::Cache {
+                        age,
+                        fetch_error: e.to_string(),
+                    },
+                )),
+                None => Err(e),
+            },
+        }
+    }
+
+    /// Store a last-known-good copy of the requirements
+    fn write_cache(path: &Path, requirements: &Self, now: u64) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let cached = CachedRequirements {
+            fetched_at: now,
+            requirements: requirements.clone(),
+        };
+        let json = serde_json::to_string_pretty(&cached)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        fs::write(path, json)
+    }
+
+    /// Read the cached requirements and their age, if present and not older than `ttl`
+    fn read_cache(path: &Path, ttl: Duration, now: u64) -> Option<(Self, Duration)> {
+        let contents = fs::read_to_string(path).ok()?;
+        let cached: CachedRequirements = serde_json::from_str(&contents).ok()?;
+        let age = Duration::from_secs(now.saturating_sub(cached.fetched_at));
+        (age <= ttl).then_some((cached.requirements, age))
+    }
+
+    fn http_client() -> Client {
+        Client::builder()
             .timeout(CONFIG_TIMEOUT)
             .user_agent("nexus-cli/version-checker")
             .build()
-            .expect("Failed to create HTTP client");
+            .expect("Failed to create HTTP client")
+    }
+
+    /// Fetch version requirements from remote config with multiple fallbacks
+    /// Priority: Firebase Hosting -> Cloud Function Cache -> GitHub
+    pub async fn fetch() -> Result<Self, VersionRequirementsError> {
+        let client = Self::http_client();
 
         // Try primary URL first (Firebase Hosting)
         match Self::fetch_from_url(&client, PRIMARY_CONFIG_URL).await {
@@ -201,9 +339,97 @@ impl VersionRequirements {
     }
 }
 
+/// Current Unix time in seconds
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_requirements_source_parsing() {
+        assert_eq!(
+            RequirementsSource::from("embedded"),
+            RequirementsSource::Embedded
+        );
+        assert_eq!(
+            RequirementsSource::from("https://mirror.internal/version.json"),
+            RequirementsSource::Url("https://mirror.internal/version.json".to_string())
+        );
+        assert_eq!(
+            RequirementsSource::from("/etc/nexus/version.json"),
+            RequirementsSource::File(PathBuf::from("/etc/nexus/version.json"))
+        );
+        assert_eq!(RequirementsSource::from(""), RequirementsSource::Remote);
+    }
+
+    #[tokio::test]
+    async fn test_load_from_file_and_embedded() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("version.json");
+        fs::write(
+            &path,
+            r#"{ "version_constraints": [{ "version": "0.1.0", "type": "blocking", "message": "m" }] }"#,
+        )
+        .unwrap();
+        let cache_path = dir.path().join("cache.json");
+
+        let (requirements, origin) =
+            VersionRequirements::load(&RequirementsSource::File(path), &cache_path)
+                .await
+                .unwrap();
+        assert_eq!(origin, RequirementsOrigin::File);
+        assert_eq!(requirements.version_constraints.len(), 1);
+
+        let (requirements, origin) =
+            VersionRequirements::load(&RequirementsSource::Embedded, &cache_path)
+                .await
+                .unwrap();
+        assert_eq!(origin, RequirementsOrigin::Embedded);
+        assert!(
+            requirements
+                .version_constraints
+                .iter()
+                .any(|constraint| constraint.constraint_type == ConstraintType::Blocking)
+        );
+        // This binary itself meets every blocking constraint it embeds
+        let check = requirements
+            .check_version_constraints(env!("CARGO_PKG_VERSION"), None, None)
+            .unwrap();
+        assert!(check.is_none_or(|check| check.constraint_type != ConstraintType::Blocking));
+    }
+
+    #[test]
+    fn test_cache_respects_ttl() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("nested").join("cache.json");
+        let requirements = VersionRequirements {
+            version_constraints: vec![VersionConstraint {
+                version: "0.9.0".to_string(),
+                constraint_type: ConstraintType::Blocking,
+                message: "Blocking".to_string(),
+                start_date: None,
+            }],
+        };
+
+        VersionRequirements::write_cache(&path, &requirements, 1_000).unwrap();
+
+        let ttl = Duration::from_secs(100);
+        let (cached, age) = VersionRequirements::read_cache(&path, ttl, 1_050).unwrap();
+        assert_eq!(cached, requirements);
+        assert_eq!(age, Duration::from_secs(50));
+
+        assert!(VersionRequirements::read_cache(&path, ttl, 1_101).is_none());
+        assert!(
+            VersionRequirements::read_cache(&dir.path().join("missing.json"), ttl, 1_050).is_none()
+        );
+    }
 
     #[test]
     fn test_version_comparison() {
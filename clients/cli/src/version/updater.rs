@@ -0,0 +1,377 @@
+//! Self-update
+//!
+//! Downloads the release artifact matching this build's platform, verifies it against the
+//! `.sha256` checksum file published with every release, and atomically swaps the running
+//! executable for it.
+
+use super::checker::{GITHUB_RELEASES_URL, GitHubRelease, VersionInfo};
+use cfg_if::cfg_if;
+use reqwest::{Client, ClientBuilder};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+use thiserror::Error;
+
+// GitHub API endpoint listing recent releases, including pre-releases
+const GITHUB_RELEASES_LIST_URL: &str =
+    "https://api.github.com/repos/nexus-xyz/nexus-cli/releases?per_page=20";
+
+// Release binaries are tens of megabytes, so allow for slow connections
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Release channel to update from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReleaseChannel {
+    /// Latest stable release
+    Stable,
+    /// Latest release, including pre-releases
+    Pre,
+}
+
+#[derive(Error, Debug)]
+pub enum UpdateError {
+    #[error("Failed to fetch release information: {0}")]
+    Fetch(String),
+
+    #[error("No release artifact is published for this platform ({0})")]
+    UnsupportedPlatform(String),
+
+    #[error("Release {release} has no artifact named {artifact}")]
+    MissingArtifact { release: String, artifact: String },
+
+    #[error("Malformed checksum file for {0}")]
+    MalformedChecksum(String),
+
+    #[error("Checksum mismatch for {artifact}: expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        artifact: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Outcome of an update run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateOutcome {
+    /// The running version is already the newest on the channel
+    UpToDate { latest: String },
+    /// A newer version exists but was not installed (check-only mode)
+    Available { latest: String, release_url: String },
+    /// The newer version was downloaded, verified and installed
+    Updated { latest: String },
+}
+
+/// Name of the release artifact built for this platform, matching the names produced by
+/// the release workflow.
+pub fn artifact_name() -> Option<&'static str> {
+    cfg_if! {
+        if #[cfg(all(target_os = "linux", target_arch = "x86_64"))] {
+            Some("nexus-network-linux-x86_64")
+        } else if #[cfg(all(target_os = "linux", target_arch = "aarch64"))] {
+            Some("nexus-network-linux-arm64")
+        } else if #[cfg(all(target_os = "macos", target_arch = "x86_64"))] {
+            Some("nexus-network-macos-x86_64")
+        } else if #[cfg(all(target_os = "macos", target_arch = "aarch64"))] {
+            Some("nexus-network-macos-arm64")
+        } else if #[cfg(all(target_os = "windows", target_arch = "x86_64"))] {
+            Some("nexus-network-windows-x86_64.exe")
+        } else {
+            None
+        }
+    }
+}
+
+/// Check for, and unless `check_only` is set, install the newest release on `channel`.
+pub async fn self_update(
+    current_version: &str,
+    channel: ReleaseChannel,
+    check_only: bool,
+) -> Result<UpdateOutcome, UpdateError> {
+    let client = ClientBuilder::new()
+        .timeout(DOWNLOAD_TIMEOUT)
+        .user_agent(format!("nexus-cli/{}", current_version))
+        .build()?;
+
+    let release = fetch_release(&client, channel).await?;
+    let mut version_info = VersionInfo::new(current_version.to_string());
+    version_info.update_from_release(release.clone());
+
+    if !version_info.update_available {
+        return Ok(UpdateOutcome::UpToDate {
+            latest: release.tag_name,
+        });
+    }
+
+    if check_only {
+        return Ok(UpdateOutcome::Available {
+            latest: release.tag_name,
+            release_url: release.html_url,
+        });
+    }
+
+    let artifact = artifact_name().ok_or_else(|| {
+        UpdateError::UnsupportedPlatform(format!(
+            "{}-{}",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        ))
+    })?;
+    let binary_url = asset_url(&release, artifact)?;
+    let checksum_url = asset_url(&release, &format!("{}.sha256", artifact))?;
+
+    let checksum_file = download(&client, &checksum_url).await?;
+    let expected = parse_checksum(&String::from_utf8_lossy(&checksum_file))
+        .ok_or_else(|| UpdateError::MalformedChecksum(artifact.to_string()))?;
+    let binary = download(&client, &binary_url).await?;
+    verify_checksum(artifact, &binary, &expected)?;
+
+    replace_executable(&std::env::current_exe()?, &binary)?;
+
+    Ok(UpdateOutcome::Updated {
+        latest: release.tag_name,
+    })
+}
+
+/// Fetch the newest release on the given channel
+async fn fetch_release(
+    client: &Client,
+    channel: ReleaseChannel,
+) -> Result<GitHubRelease, UpdateError> {
+    let url = match channel {
+        ReleaseChannel::Stable => GITHUB_RELEASES_URL,
+        ReleaseChannel::Pre => GITHUB_RELEASES_LIST_URL,
+    };
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        return Err(UpdateError::Fetch(format!(
+            "GitHub API returned status: {}",
+            response.status()
+        )));
+    }
+
+    match channel {
+        ReleaseChannel::Stable => Ok(response.json::<GitHubRelease>().await?),
+        ReleaseChannel::Pre => {
+            // Releases are listed newest first
+            let releases: Vec<GitHubRelease> = response.json().await?;
+            releases
+                .into_iter()
+                .next()
+                .ok_or_else(|| UpdateError::Fetch("No releases found".to_string()))
+        }
+    }
+}
+
+/// Find the download URL of the named asset in a release
+fn asset_url(release: &GitHubRelease, name: &str) -> Result<String, UpdateError> {
+    release
+        .assets
+        .iter()
+        .find(|asset| asset.name == name)
+        .map(|asset| asset.browser_download_url.clone())
+        .ok_or_else(|| UpdateError::MissingArtifact {
+            release: release.tag_name.clone(),
+            artifact: name.to_string(),
+        })
+}
+
+async fn download(client: &Client, url: &str) -> Result<Vec<u8>, UpdateError> {
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        return Err(UpdateError::Fetch(format!(
+            "Download of {} returned status: {}",
+            url,
+            response.status()
+        )));
+    }
+    Ok(response.bytes().await?.to_vec())
+}
+
+/// Parse a checksum file in either bare (`<hex>`) or `sha256sum` (`<hex>  <file>`) format
+fn parse_checksum(contents: &str) -> Option<String> {
+    let digest = contents.split_whitespace().next()?.to_lowercase();
+    if digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(digest)
+    } else {
+        None
+    }
+}
+
+fn verify_checksum(artifact: &str, bytes: &[u8], expected: &str) -> Result<(), UpdateError> {
+    let actual = format!("{:x}", Sha256::digest(bytes));
+    if actual != expected {
+        return Err(UpdateError::ChecksumMismatch {
+            artifact: artifact.to_string(),
+            expected: expected.to_string(),
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Atomically replace the executable at `target` with `bytes`.
+///
+/// The new binary is staged next to the target (so the final rename stays on one
+/// filesystem), flushed to disk, and then renamed over the target.
+pub fn replace_executable(target: &Path, bytes: &[u8]) -> Result<(), UpdateError> {
+    let file_name = target
+        .file_name()
+        .ok_or_else(|| std::io::Error::other("Executable path has no file name"))?
+        .to_string_lossy()
+        .to_string();
+    let staged = target.with_file_name(format!(".{}.update-{}", file_name, std::process::id()));
+
+    let result = stage_and_swap(target, &staged, bytes);
+    if result.is_err() {
+        let _ = fs::remove_file(&staged);
+    }
+    result
+}
+
+fn stage_and_swap(target: &Path, staged: &Path, bytes: &[u8]) -> Result<(), UpdateError> {
+    let mut file = fs::File::create(staged)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    drop(file);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(target)
+            .map(|metadata| metadata.permissions().mode())
+            .unwrap_or(0o755);
+        fs::set_permissions(staged, fs::Permissions::from_mode(mode | 0o111))?;
+    }
+
+    #[cfg(windows)]
+    {
+        // A running executable cannot be overwritten on Windows, but it can be renamed
+        let previous = target.with_file_name(format!("{}.old", file_name_of(target)));
+        let _ = fs::remove_file(&previous);
+        fs::rename(target, &previous)?;
+    }
+
+    fs::rename(staged, target)?;
+    Ok(())
+}
+
+#[cfg(windows)]
+fn file_name_of(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Replace the current process with a fresh run of the (updated) executable, passing
+/// along the original arguments. Only returns if the re-exec failed.
+pub fn reexec() -> UpdateError {
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => return e.into(),
+    };
+    let mut command = std::process::Command::new(exe);
+    command.args(std::env::args_os().skip(1));
+
+    cfg_if! {
+        if #[cfg(unix)] {
+            use std::os::unix::process::CommandExt;
+            command.exec().into()
+        } else {
+            match command.status() {
+                Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+                Err(e) => e.into(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::checker::GitHubAsset;
+    use tempfile::tempdir;
+
+    fn release_with_assets(names: &[&str]) -> GitHubRelease {
+        GitHubRelease {
+            tag_name: "v1.0.0".to_string(),
+            name: "Release v1.0.0".to_string(),
+            published_at: "2025-01-01T00:00:00Z".to_string(),
+            html_url: "https://github.com/nexus-xyz/nexus-cli/releases/tag/v1.0.0".to_string(),
+            prerelease: false,
+            assets: names
+                .iter()
+                .map(|name| GitHubAsset {
+                    name: name.to_string(),
+                    browser_download_url: format!("https://example.com/{}", name),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_checksum_formats() {
+        let digest = "a".repeat(64);
+        assert_eq!(parse_checksum(&digest), Some(digest.clone()));
+        assert_eq!(
+            parse_checksum(&format!(
+                "{}  nexus-network-linux-x86_64\n",
+                digest.to_uppercase()
+            )),
+            Some(digest)
+        );
+        assert_eq!(parse_checksum("not-a-checksum"), None);
+        assert_eq!(parse_checksum(""), None);
+    }
+
+    #[test]
+    fn test_verify_checksum() {
+        let bytes = b"nexus";
+        let expected = format!("{:x}", Sha256::digest(bytes));
+        assert!(verify_checksum("artifact", bytes, &expected).is_ok());
+        assert!(matches!(
+            verify_checksum("artifact", b"tampered", &expected),
+            Err(UpdateError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_asset_url_lookup() {
+        let release = release_with_assets(&["nexus-network-linux-x86_64"]);
+        assert_eq!(
+            asset_url(&release, "nexus-network-linux-x86_64").unwrap(),
+            "https://example.com/nexus-network-linux-x86_64"
+        );
+        assert!(matches!(
+            asset_url(&release, "nexus-network-linux-x86_64.sha256"),
+            Err(UpdateError::MissingArtifact { .. })
+        ));
+    }
+
+    #[test]
+    fn test_replace_executable_swaps_contents() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("nexus-network");
+        fs::write(&target, b"old binary").unwrap();
+
+        replace_executable(&target, b"new binary").unwrap();
+
+        assert_eq!(fs::read(&target).unwrap(), b"new binary");
+        // Only the target should remain; the staged file is renamed away
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&target).unwrap().permissions().mode();
+            assert_ne!(mode & 0o111, 0, "replaced binary should be executable");
+        }
+    }
+}
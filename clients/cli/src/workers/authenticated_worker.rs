@@ -4,11 +4,14 @@ use super::core::{EventSender, WorkerConfig};
 use super::fetcher::TaskFetcher;
 use super::prover::TaskProver;
 use super::submitter::ProofSubmitter;
+use crate::consts::cli_consts::MAX_CONSECUTIVE_PROVING_FAILURES;
 use crate::events::{Event, ProverState};
 use crate::orchestrator::OrchestratorClient;
+use crate::run_summary::{RunStats, SharedRunStats, StopReason};
+use crate::schedule::Scheduler;
 
 use ed25519_dalek::SigningKey;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::{broadcast, mpsc};
 use tokio::task::JoinHandle;
 
@@ -17,13 +20,17 @@ pub struct AuthenticatedWorker {
     fetcher: TaskFetcher,
     prover: TaskProver,
     submitter: ProofSubmitter,
+    scheduler: Scheduler,
     event_sender: EventSender,
     max_tasks: Option<u32>,
     tasks_completed: u32,
+    consecutive_failures: u32,
+    stats: SharedRunStats,
     shutdown_sender: broadcast::Sender<()>,
 }
 
 impl AuthenticatedWorker {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         node_id: u64,
         signing_key: SigningKey,
@@ -31,6 +38,7 @@ impl AuthenticatedWorker {
         config: WorkerConfig,
         event_sender: mpsc::Sender<Event>,
         max_tasks: Option<u32>,
+        stats: SharedRunStats,
         shutdown_sender: broadcast::Sender<()>,
     ) -> Self {
         let event_sender_helper = EventSender::new(event_sender);
@@ -44,6 +52,7 @@ impl AuthenticatedWorker {
             &config,
         );
 
+        let scheduler = Scheduler::new(config.proving.schedule.clone());
         let prover = TaskProver::new(event_sender_helper.clone(), config.clone());
 
         let submitter = ProofSubmitter::new(
@@ -57,9 +66,12 @@ impl AuthenticatedWorker {
             fetcher,
             prover,
             submitter,
+            scheduler,
             event_sender: event_sender_helper,
             max_tasks,
             tasks_completed: 0,
+            consecutive_failures: 0,
+            stats,
             shutdown_sender,
         }
     }
@@ -96,12 +108,38 @@ impl AuthenticatedWorker {
         join_handles
     }
 
+    /// Record a stage in the run statistics
+    fn record(&self, record: impl FnOnce(&mut RunStats)) {
+        if let Ok(mut stats) = self.stats.lock() {
+            record(&mut stats);
+        }
+    }
+
+    /// Stop the worker and the application, recording why
+    async fn stop(&mut self, reason: StopReason, message: String) {
+        self.record(|stats| stats.stop(reason));
+        self.event_sender
+            .send_event(Event::state_change(ProverState::Waiting, message))
+            .await;
+
+        // Send shutdown signal to trigger application exit
+        let _ = self.shutdown_sender.send(());
+    }
+
     /// Complete work cycle: fetch→prove→submit
-    /// Returns true if the worker should exit (max tasks reached)
+    /// Returns true if the worker should exit (max tasks reached, or proving keeps failing)
     async fn work_cycle(&mut self) -> bool {
+        // Hold back while the schedule does not allow proving or the machine is too hot
+        self.scheduler.wait_until_allowed(&self.event_sender).await;
+        self.prover.cool_down().await;
+
         // Step 1: Fetch task
         let task = match self.fetcher.fetch_task().await {
-            Ok(task) => task,
+            Ok(task) => {
+                let took = self.fetcher.last_fetch_time();
+                self.record(|stats| stats.record_fetch(took));
+                task
+            }
             Err(_) => {
                 // Error already logged in fetcher, wait before retry
                 tokio::time::sleep(Duration::from_secs(1)).await;
@@ -118,9 +156,33 @@ impl AuthenticatedWorker {
             ))
             .await;
 
+        let started = Instant::now();
         let proof_result = match self.prover.prove_task(&task).await {
-            Ok(proof_result) => proof_result,
+            Ok(proof_result) => {
+                self.fetcher.record_proving_time(started.elapsed());
+                self.record(|stats| stats.record_proof(started.elapsed(), true));
+                self.consecutive_failures = 0;
+                proof_result
+            }
             Err(_) => {
+                self.record(|stats| stats.record_proof(started.elapsed(), false));
+                self.consecutive_failures += 1;
+
+                // A batch run gives up instead of retrying forever
+                if self.max_tasks.is_some()
+                    && self.consecutive_failures >= MAX_CONSECUTIVE_PROVING_FAILURES
+                {
+                    self.stop(
+                        StopReason::ProvingFailures,
+                        format!(
+                            "{} proofs failed in a row, shutting down",
+                            self.consecutive_failures
+                        ),
+                    )
+                    .await;
+                    return true;
+                }
+
                 // Send state change back to Waiting on proof failure
                 self.event_sender
                     .send_event(Event::state_change(
@@ -133,7 +195,9 @@ impl AuthenticatedWorker {
         };
 
         // Step 3: Submit proof
+        let started = Instant::now();
         let submission_result = self.submitter.submit_proof(&task, &proof_result).await;
+        self.record(|stats| stats.record_submission(started.elapsed(), submission_result.is_ok()));
 
         // Only increment task counter on successful submission
         if submission_result.is_ok() {
@@ -146,15 +210,11 @@ impl AuthenticatedWorker {
                     // before triggering shutdown
                     tokio::time::sleep(std::time::Duration::from_millis(100)).await;
 
-                    self.event_sender
-                        .send_event(Event::state_change(
-                            ProverState::Waiting,
-                            format!("Completed {} tasks, shutting down", self.tasks_completed),
-                        ))
-                        .await;
-
-                    // Send shutdown signal to trigger application exit
-                    let _ = self.shutdown_sender.send(());
+                    self.stop(
+                        StopReason::Completed,
+                        format!("Completed {} tasks, shutting down", self.tasks_completed),
+                    )
+                    .await;
                     return true; // Signal to exit the worker loop
                 }
             }
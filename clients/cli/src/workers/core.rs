@@ -1,7 +1,12 @@
 //! Core worker utilities and traits
 
 use crate::events::{Event, EventType};
+use crate::fetch_cadence::CadenceBounds;
 use crate::logging::LogLevel;
+use crate::proof_archive::ProofArchive;
+use crate::schedule::ScheduleConfig;
+use crate::thermal::ThermalConfig;
+use std::path::PathBuf;
 use tokio::sync::mpsc;
 
 /// Common event sending utilities for workers
@@ -64,18 +69,45 @@ impl EventSender {
     }
 }
 
+/// Proving options chosen on the command line
+#[derive(Clone, Debug, Default)]
+pub struct ProvingOptions {
+    /// Submit proofs without verifying them locally first
+    pub skip_local_verify: bool,
+    /// Where to keep a copy of every generated proof
+    pub proof_archive: Option<ProofArchive>,
+    /// When new tasks may be fetched
+    pub schedule: ScheduleConfig,
+    /// When proving backs off to let the machine cool down
+    pub thermal: ThermalConfig,
+    /// Shortest and longest wait between successful task fetches
+    pub fetch_cadence: CadenceBounds,
+    /// Where to save tasks that fail to prove, for replaying them later
+    pub capture_failed: Option<PathBuf>,
+}
+
 /// Worker configuration shared across all worker types
 #[derive(Clone)]
 pub struct WorkerConfig {
     pub environment: crate::environment::Environment,
     pub client_id: String,
+    /// Most prover threads to run at once, from `--max-threads`
+    pub num_workers: usize,
+    pub proving: ProvingOptions,
 }
 
 impl WorkerConfig {
-    pub fn new(environment: crate::environment::Environment, client_id: String) -> Self {
+    pub fn new(
+        environment: crate::environment::Environment,
+        client_id: String,
+        num_workers: usize,
+        proving: ProvingOptions,
+    ) -> Self {
         Self {
             environment,
             client_id,
+            num_workers,
+            proving,
         }
     }
 }
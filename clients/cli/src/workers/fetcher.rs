@@ -3,13 +3,15 @@
 use super::core::{EventSender, WorkerConfig};
 use crate::analytics::track_got_task;
 use crate::consts::cli_consts::{rate_limiting, task_fetching};
-use crate::events::EventType;
+use crate::events::{EventType, Worker};
+use crate::fetch_cadence::{FetchCadence, WaitReason};
 use crate::logging::LogLevel;
-use crate::network::{NetworkClient, RequestTimer, RequestTimerConfig};
+use crate::network::{NetworkClient, Operation, RequestTimer, RequestTimerConfig, RetryPolicy};
 use crate::orchestrator::Orchestrator;
+use crate::orchestrator::error::ServerError;
 use crate::task::Task;
 use ed25519_dalek::VerifyingKey;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tokio::time::sleep;
 
@@ -27,6 +29,10 @@ pub struct TaskFetcher {
     network_client: NetworkClient,
     event_sender: EventSender,
     config: WorkerConfig,
+    cadence: FetchCadence,
+    wait_reason: WaitReason,
+    /// Time spent in the last fetch request, excluding the wait before it
+    last_fetch_time: Duration,
 }
 
 impl TaskFetcher {
@@ -37,9 +43,11 @@ impl TaskFetcher {
         event_sender: EventSender,
         config: &WorkerConfig,
     ) -> Self {
-        // Configure request timer for task fetching
+        // Configure request timer for task fetching; the cadence sets the wait after each
+        // successful fetch, so the timer only enforces its lower bound
+        let bounds = config.proving.fetch_cadence;
         let timer_config = RequestTimerConfig::combined(
-            task_fetching::rate_limit_interval(),
+            bounds.min,
             rate_limiting::TASK_FETCH_MAX_REQUESTS_PER_WINDOW,
             rate_limiting::task_fetch_window(),
             task_fetching::initial_backoff(), // Use as default retry delay
@@ -47,7 +55,11 @@ impl TaskFetcher {
         let request_timer = RequestTimer::new(timer_config);
 
         // Create network client with retry logic
-        let network_client = NetworkClient::new(request_timer, task_fetching::MAX_RETRIES);
+        let network_client = NetworkClient::new(
+            request_timer,
+            RetryPolicy::for_operation(Operation::FetchTask),
+        )
+        .with_event_sender(event_sender.clone(), Worker::TaskFetcher);
 
         Self {
             node_id,
@@ -56,9 +68,22 @@ impl TaskFetcher {
             network_client,
             event_sender,
             config: config.clone(),
+            cadence: FetchCadence::new(bounds),
+            wait_reason: WaitReason::MinimumInterval,
+            last_fetch_time: Duration::ZERO,
         }
     }
 
+    /// Let the fetch cadence follow how long tasks take to prove
+    pub fn record_proving_time(&mut self, took: Duration) {
+        self.cadence.record_proving_time(took);
+    }
+
+    /// Time the last fetch spent talking to the orchestrator, not waiting for its turn
+    pub fn last_fetch_time(&self) -> Duration {
+        self.last_fetch_time
+    }
+
     /// Fetch a single task with automatic retry and proper logging
     pub async fn fetch_task(&mut self) -> Result<Task, FetchError> {
         // Check if we can proceed immediately
@@ -82,8 +107,9 @@ impl TaskFetcher {
                 self.event_sender
                     .send_task_event(
                         format!(
-                            "Step 1 of 4: Waiting - ready for next task ({}) seconds",
-                            wait_time.as_secs()
+                            "Step 1 of 4: Waiting - ready for next task ({}) seconds - {}",
+                            wait_time.as_secs(),
+                            self.wait_reason
                         ),
                         EventType::Waiting,
                         LogLevel::Info,
@@ -94,16 +120,22 @@ impl TaskFetcher {
         }
 
         // Attempt to fetch task through network client
-        match self
+        let started = Instant::now();
+        let result = self
             .network_client
             .fetch_task(
                 self.orchestrator.as_ref(),
                 &self.node_id.to_string(),
                 self.verifying_key,
             )
-            .await
-        {
+            .await;
+        self.last_fetch_time = started.elapsed();
+        match result {
             Ok(task) => {
+                let (delay, reason) = self.cadence.next_delay(task.retry_after, Instant::now());
+                self.network_client.request_timer_mut().delay_next(delay);
+                self.wait_reason = reason;
+
                 // Log successful fetch
                 self.event_sender
                     .send_task_event(
@@ -123,6 +155,13 @@ impl TaskFetcher {
                 Ok(task)
             }
             Err(e) => {
+                self.wait_reason = match e.server_error() {
+                    Some(ServerError::RateLimited) => {
+                        WaitReason::RateLimited(self.cadence.record_rate_limited(Instant::now()))
+                    }
+                    _ => WaitReason::Retrying,
+                };
+
                 // Log fetch failure with appropriate level
                 let log_level = self.network_client.classify_error(&e);
                 self.event_sender
@@ -2,10 +2,13 @@
 
 use super::core::{EventSender, WorkerConfig};
 use crate::analytics::track_authenticated_proof_analytics;
-use crate::events::EventType;
+use crate::events::{Event, EventType, TaskFailure};
 use crate::logging::LogLevel;
+use crate::prover::child::ProverPool;
 use crate::prover::{ProverError, ProverResult, authenticated_proving};
 use crate::task::Task;
+use crate::task_capture::TaskCapture;
+use crate::thermal::ThermalMonitor;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -18,20 +21,39 @@ pub enum ProveError {
 pub struct TaskProver {
     event_sender: EventSender,
     config: WorkerConfig,
+    /// Long-lived proving subprocesses, one per input proven at once
+    provers: ProverPool,
+    /// Eases off proving while the machine runs hot
+    thermal: ThermalMonitor,
 }
 
 impl TaskProver {
     pub fn new(event_sender: EventSender, config: WorkerConfig) -> Self {
         Self {
+            provers: ProverPool::for_available_memory(
+                config.num_workers,
+                Some((event_sender.clone(), 0)),
+            ),
+            thermal: ThermalMonitor::new(config.proving.thermal.clone()),
             event_sender,
             config,
         }
     }
 
     /// Generate proof for a task with proper logging
-    pub async fn prove_task(&self, task: &Task) -> Result<ProverResult, ProveError> {
+    pub async fn prove_task(&mut self, task: &Task) -> Result<ProverResult, ProveError> {
         // Use existing prover module for proof generation
-        match authenticated_proving(task, &self.config.environment, &self.config.client_id).await {
+        match authenticated_proving(
+            &mut self.provers,
+            &mut self.thermal,
+            task,
+            &self.config.environment,
+            &self.config.client_id,
+            &self.event_sender,
+            !self.config.proving.skip_local_verify,
+        )
+        .await
+        {
             Ok((proofs, combined_hash, individual_proof_hashes)) => {
                 // Log successful proof generation
                 self.event_sender
@@ -47,27 +69,97 @@ impl TaskProver {
                     task.clone(),
                     self.config.environment.clone(),
                     self.config.client_id.clone(),
+                    !self.config.proving.skip_local_verify,
                 ));
 
-                Ok(ProverResult {
+                let result = ProverResult {
                     proofs,
                     combined_hash,
                     individual_proof_hashes,
-                })
+                };
+                self.keep_proofs(task, &result).await;
+                Ok(result)
             }
             Err(e) => {
-                // Log proof generation failure
+                // Log proof generation failure, keeping the prover's last words with it
                 self.event_sender
-                    .send_prover_event(
+                    .send_event(Event::task_failed(
                         0, // Single-threaded prover for now
-                        format!("Proof generation failed for task {}: {}", task.task_id, e),
-                        EventType::Error,
-                        LogLevel::Error,
-                    )
+                        TaskFailure {
+                            task_id: task.task_id.clone(),
+                            error: e.to_string(),
+                            stderr_tail: e.stderr_tail().to_vec(),
+                        },
+                    ))
                     .await;
+                self.capture_failure(task, &e).await;
 
                 Err(ProveError::Generation(e))
             }
         }
     }
+
+    /// Pause until the machine is cool enough for another task
+    pub async fn cool_down(&mut self) {
+        self.thermal.cool_down(&self.event_sender).await;
+    }
+
+    /// Save a task that failed to prove for `nexus-network replay`, if capturing was selected
+    async fn capture_failure(&self, task: &Task, error: &ProverError) {
+        let Some(dir) = &self.config.proving.capture_failed else {
+            return;
+        };
+        let (message, log_level) = match TaskCapture::new(task, error).save(dir) {
+            Ok(path) => (
+                format!(
+                    "Captured failed task {} in {}; reproduce it with `nexus-network replay {}`",
+                    task.task_id,
+                    path.display(),
+                    path.display()
+                ),
+                LogLevel::Info,
+            ),
+            Err(e) => (
+                format!(
+                    "Could not capture failed task {} in {}: {}",
+                    task.task_id,
+                    dir.display(),
+                    e
+                ),
+                LogLevel::Warn,
+            ),
+        };
+        self.event_sender
+            .send_prover_event(0, message, EventType::Refresh, log_level)
+            .await;
+    }
+
+    /// Write the task's proofs to the proof archive, if one was selected
+    async fn keep_proofs(&self, task: &Task, result: &ProverResult) {
+        let Some(archive) = &self.config.proving.proof_archive else {
+            return;
+        };
+        let (message, log_level) = match archive.save(task, result) {
+            Ok(bundle) => (
+                format!(
+                    "Kept proofs for task {} in {}",
+                    task.task_id,
+                    bundle.display()
+                ),
+                LogLevel::Debug,
+            ),
+            Err(e) => (
+                format!(
+                    "Could not keep proofs for task {} in {}: {}",
+                    task.task_id,
+                    archive.dir().display(),
+                    e
+                ),
+                LogLevel::Warn,
+            ),
+        };
+        self.event_sender
+            .send_prover_event(0, message, EventType::Refresh, log_level)
+            .await;
+    }
 }
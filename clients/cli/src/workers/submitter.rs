@@ -5,9 +5,12 @@ use crate::analytics::{
     track_proof_accepted, track_proof_submission_error, track_proof_submission_success,
 };
 use crate::consts::cli_consts::{proof_submission, rate_limiting};
-use crate::events::EventType;
+use crate::events::{EventType, Worker};
 use crate::logging::LogLevel;
-use crate::network::{NetworkClient, ProofSubmission, RequestTimer, RequestTimerConfig};
+use crate::network::{
+    ErrorClass, NetworkClient, Operation, ProofSubmission, RequestTimer, RequestTimerConfig,
+    RetryPolicy, SubmissionOutcome,
+};
 use crate::orchestrator::Orchestrator;
 use crate::prover::ProverResult;
 use crate::task::Task;
@@ -48,7 +51,11 @@ impl ProofSubmitter {
         let request_timer = RequestTimer::new(timer_config);
 
         // Create network client with more retries for critical submissions
-        let network_client = NetworkClient::new(request_timer, proof_submission::MAX_RETRIES);
+        let network_client = NetworkClient::new(
+            request_timer,
+            RetryPolicy::for_operation(Operation::SubmitProof),
+        )
+        .with_event_sender(event_sender.clone(), Worker::ProofSubmitter);
 
         Self {
             signing_key,
@@ -111,12 +118,14 @@ impl ProofSubmitter {
             )
             .await
         {
-            Ok(attempts) => {
+            Ok((outcome, attempts)) => {
                 // Log successful submission with attempt count
-                let attempt_text = if attempts == 1 {
-                    "".to_string()
-                } else {
-                    format!(" (after {} attempts)", attempts)
+                let attempt_text = match (outcome, attempts) {
+                    (SubmissionOutcome::AlreadySubmitted, _) => {
+                        " (already received by orchestrator)".to_string()
+                    }
+                    (SubmissionOutcome::Accepted, 1) => "".to_string(),
+                    (SubmissionOutcome::Accepted, _) => format!(" (after {} attempts)", attempts),
                 };
 
                 self.event_sender
@@ -137,6 +146,21 @@ impl ProofSubmitter {
 
                 Ok(())
             }
+            Err((e, _)) if self.network_client.error_class(&e) == ErrorClass::Obsolete => {
+                // The task expired before the proof arrived; nothing left to retry
+                self.event_sender
+                    .send_proof_event(
+                        format!(
+                            "Task {} expired before its proof was submitted; dropping it",
+                            task.task_id
+                        ),
+                        EventType::Refresh,
+                        LogLevel::Info,
+                    )
+                    .await;
+
+                Err(SubmitError::Network(e))
+            }
             Err((e, attempts)) => {
                 // Log submission failure with attempt count and appropriate level
                 let log_level = self.network_client.classify_error(&e);